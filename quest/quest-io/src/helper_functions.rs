@@ -1,8 +1,48 @@
-use gstd::ActorId;
-use crate::SkillNFT;
+use gstd::{msg, prelude::*, ActorId};
+use reputation_io::{MTKAction, MTKError, MTKEvent, SubmissionRef, TokenId};
+use crate::{QuestId, SkillNFT};
+use sha2::{Digest, Sha256};
 
-pub(crate) fn check_skill_nft(_holder: ActorId, _skill_tags: SkillNFT) -> bool {
-    true
+/// Ask the reputation contract whether `holder` owns the skill NFT `token_id` of the given skill.
+/// Any failure in reaching the reputation contract is treated as a failed verification.
+pub async fn check_skill_nft(reputation_contract: ActorId, holder: ActorId, token_id: TokenId, skill: SkillNFT) -> bool {
+    let reply = msg::send_for_reply_as::<_, Result<MTKEvent, MTKError>>(
+        reputation_contract,
+        MTKAction::VerifyReputation {
+            target: holder,
+            skill_type: true,
+            token_id,
+            title: Some(skill.name()),
+        },
+        0,
+        0,
+    );
+
+    match reply {
+        Ok(future) => matches!(future.await, Ok(Ok(MTKEvent::RepuVerified { .. }))),
+        Err(_) => false,
+    }
+}
+
+/// Commitment to a submission of a commit-reveal quest: SHA-256 of a domain tag, the SCALE-encoded quest id, seeker
+/// and submission, followed by the salt.
+/// Seekers compute it off-chain to seal their submission, the contract recomputes it on reveal.
+/// The commitment is bound to the seeker, so revealed submissions can't be replayed by other seekers.
+pub fn seal_submission(quest_id: &QuestId, seeker: ActorId, submission: &SubmissionRef, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"quest/seal-submission");
+    hasher.update((quest_id, seeker, submission).encode());
+    hasher.update(salt);
+    hasher.finalize().into()
 }
 
-pub(crate) fn consume_skill_nft(_holder: ActorId, _skill_tags: SkillNFT) {}
\ No newline at end of file
+/// Hash of the passcode of a dedicated quest: SHA-256 of a domain tag, the salt chosen by the provider and the passcode.
+/// Providers publish the salt and the hash, and hand the passcode to seekers.
+/// A fresh salt for every quest keeps the hashes of common passcodes from being looked up or matched across quests.
+pub fn hash_passcode(salt: &[u8; 32], passcode: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"quest/passcode");
+    hasher.update(salt);
+    hasher.update(passcode.as_bytes());
+    hasher.finalize().into()
+}
@@ -1,16 +1,27 @@
 #![no_std]
-use gstd::{collections::BTreeMap, prelude::*, ActorId};
+use gstd::{collections::BTreeMap, prelude::*, ActorId, MessageId};
 use gmeta::{In, InOut, Metadata, Out};
+use reputation_io::TokenId;
+pub use reputation_io::{Storage, SubmissionRef};
 
-mod helper_functions;
+pub mod helper_functions;
+use helper_functions::{hash_passcode, seal_submission};
 
 pub struct ProgramMetadata;
 pub type QuestId = String;
-pub type Submmision = String;
+
+/// Blocks after the deadline and the reveal window during which the provider must assign the winners
+/// before the prize can be refunded, about a week.
+pub const PRIZE_GRACE_PERIOD: u64 = 201_600;
+/// Milestones a quest can have at most, so every milestone index fits into `QuestEvent::MilestonePassed`.
+pub const MAX_MILESTONES: usize = u8::MAX as usize + 1;
+/// Members a team can have at most including the leader.
+/// Grading a team sends the rewards of every member with gas deposited for each reply, so it has to fit into one message.
+pub const MAX_TEAM_SIZE: u8 = 8;
 
 impl Metadata for ProgramMetadata {
     type Init = In<InitQuest>;
-    type Handle = InOut<QuestAction, QuestEvent>;
+    type Handle = InOut<QuestAction, Result<QuestEvent, QuestError>>;
     type Reply = ();
     type Others = ();
     type Signal = ();
@@ -22,6 +33,10 @@ impl Metadata for ProgramMetadata {
 pub struct InitQuest {
 	pub approved_providers: Vec<ActorId>,
 	pub minumum_free_gradings: u8,
+	/// The reputation contract that holds seekers' skill tokens and NFTs.
+	pub reputation_contract: ActorId,
+	/// Which skill FT in the reputation contract is issued for each skill token.
+	pub skill_tokens: Vec<(SkillToken, TokenId)>,
 }
 
 /// Base structure for all quests
@@ -67,47 +82,158 @@ pub struct Base {
 	// Below are dynamic informations for a quest
 
 	/// Manage submissions from seekers.
-	/// We use google drive links now and will transition to include decentralized storage in the future.
+	/// Submissions live on IPFS, Arweave or behind an HTTPS URL, pinned down by the hash of their content.
 	pub submissions: BTreeMap<ActorId, SeekerStatus>,
 	/// Manage gradings for seekers.
 	pub gradings: BTreeMap<ActorId, Option<Gradings>>,
 	/// A quest can only get modified once within a time limit start from the appearace of the first claimer.
 	pub modified: bool,
+	/// Value staked by seekers who committed without free gradings left.
+	/// Refunded on submission, forfeited to the provider if the seeker withdraws or never submits before the deadline.
+	pub stakes: BTreeMap<ActorId, u128>,
+	/// How many times the provider can ask a seeker to revise the submission before grading, per milestone.
+	pub max_revisions: u8,
+	/// Every submission of each seeker, oldest first.
+	pub submission_history: BTreeMap<ActorId, Vec<SubmissionRef>>,
+	/// Criteria the submissions are scored against, quests without a rubric are graded directly.
+	pub rubric: Option<Rubric>,
+	/// The scores and feedback given with each grade.
+	pub assessments: BTreeMap<ActorId, Assessment>,
+	/// Reviewers assigned by the provider, they can grade and request revisions.
+	pub graders: Vec<ActorId>,
+	/// Co-managers assigned by the provider, they can modify and close the quest.
+	pub managers: Vec<ActorId>,
+	/// Quests seekers need to pass before committing to this one.
+	pub prerequisites: Vec<Prerequisite>,
+	/// Staged deliverables, seekers submit and get graded for each milestone in order.
+	/// Quests without milestones are graded once.
+	pub milestones: Vec<Milestone>,
+	/// Gradings of the milestones each seeker passed so far, its length is the index of the current milestone.
+	pub milestone_gradings: BTreeMap<ActorId, Vec<Gradings>>,
+	/// Revisions requested from each seeker, counted again from zero for every milestone.
+	pub revisions: BTreeMap<ActorId, u8>,
 }
 
 impl Base {
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String> {
-		if self.submissions.get(&msg_src).unwrap() != &SeekerStatus::Waiting {
-			return Err(String::from("You have not committed to this quest!"));
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError> {
+		// Seekers submit once, and again for every revision requested by the provider
+		match self.submissions.get(&msg_src) {
+			Some(SeekerStatus::Waiting) | Some(SeekerStatus::RevisionRequested { .. }) => {},
+			_ => return Err(QuestError::NotCommitted),
 		}
+		self.submission_history.entry(msg_src).or_default().push(submission.clone());
 		self.submissions.insert(msg_src, SeekerStatus::Submitted(submission));
 		return Ok(());
 	}
 
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> Result<(), String> {
-		if self.provider != msg_src {
-			return Err(String::from("Only the provider can grade the submissions!"));
+	/// The provider and the assigned graders can grade.
+	pub fn is_grader(&self, actor: ActorId) -> bool {
+		self.provider == actor || self.graders.contains(&actor)
+	}
+
+	/// The provider and the assigned co-managers can modify and close the quest.
+	pub fn is_manager(&self, actor: ActorId) -> bool {
+		self.provider == actor || self.managers.contains(&actor)
+	}
+
+	/// Returns whether the seeker is done with the quest, rather than moved on to the next milestone.
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, gradings: Gradings) -> Result<bool, QuestError> {
+		if !self.is_grader(msg_src) {
+			return Err(QuestError::NotGrader);
 		}
-		// The gradee must have submitted to the quest
-		if self.submissions.get(&commiter).unwrap() != &SeekerStatus::Submitted(submission) {
-			return Err(String::from("The gradee has not submitted to this quest!"));
+		// The gradee must have submitted to the quest, sealed submissions must be revealed first
+		if self.submissions.get(&commiter) != Some(&SeekerStatus::Submitted(submission)) {
+			return Err(QuestError::NotSubmitted);
 		}
-		
+
+		// Passing a milestone before the last one moves the seeker on to the next milestone
+		if gradings != Gradings::Reject && self.current_milestone(commiter) + 1 < self.milestones.len() {
+			self.revisions.remove(&commiter);
+			self.milestone_gradings.entry(commiter).or_default().push(gradings);
+			self.submissions.insert(commiter, SeekerStatus::Waiting);
+			return Ok(false);
+		}
+
 		self.gradings.insert(commiter, Some(gradings.clone()));
 		self.submissions.insert(commiter, SeekerStatus::Graded(gradings));
 
+		return Ok(true);
+	}
+
+	/// Index of the milestone the seeker is working on.
+	pub fn current_milestone(&self, seeker: ActorId) -> usize {
+		self.milestone_gradings.get(&seeker).map_or(0, |gradings| gradings.len())
+	}
+
+	/// Submissions are due by the deadline of the current milestone, or the quest deadline without milestones.
+	pub fn current_deadline(&self, seeker: ActorId) -> u64 {
+		self.milestones.get(self.current_milestone(seeker)).map_or(self.deadline, |milestone| milestone.deadline)
+	}
+
+	fn request_revision(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, feedback: String) -> Result<u8, QuestError> {
+		if !self.is_grader(msg_src) {
+			return Err(QuestError::NotGrader);
+		}
+		// Only the latest submission can be revised
+		if self.submissions.get(&commiter) != Some(&SeekerStatus::Submitted(submission)) {
+			return Err(QuestError::NotSubmitted);
+		}
+
+		let revisions = self.revisions.get(&commiter).copied().unwrap_or(0);
+		if revisions >= self.max_revisions {
+			return Err(QuestError::NoRevisionsLeft);
+		}
+		let remaining_rounds = self.max_revisions - revisions - 1;
+		self.revisions.insert(commiter, revisions + 1);
+		self.submissions.insert(commiter, SeekerStatus::RevisionRequested { feedback, remaining_rounds });
+
+		return Ok(remaining_rounds);
+	}
+
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		match self.submissions.get(&msg_src) {
+			Some(SeekerStatus::Waiting) => {},
+			None | Some(SeekerStatus::Withdrawn) => return Err(QuestError::NotCommitted),
+			Some(_) => return Err(QuestError::AlreadySubmitted),
+		}
+		// The seeker stays in the submissions as withdrawn, so the withdrawal is kept in the history
+		self.submissions.insert(msg_src, SeekerStatus::Withdrawn);
+		self.gradings.remove(&msg_src);
+
 		return Ok(());
 	}
 }
 
 pub trait QuestTrait {
-	fn commit(&mut self, msg_src: ActorId) -> Result<(), String>;
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String>;
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, grading: Gradings) -> Result<(), String>;
-	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), String>;
+	fn commit(&mut self, msg_src: ActorId) -> Result<(), QuestError>;
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError>;
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, grading: Gradings) -> Result<(), QuestError>;
+	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), QuestError>;
+	/// The seeker leaves the quest before submitting, what gets refunded depends on the tier.
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError>;
 	fn get_capacity(&self) -> u32;
 	fn get_owner(&self) -> ActorId;
 	fn get_deadline(&self) -> u64;
+	fn get_base(&self) -> &Base;
+	fn get_base_mut(&mut self) -> &mut Base;
+	/// Reputation NFT issued to seekers who passed the quest, only mid-tier and top-tier quests issue one.
+	fn get_reputation_nft(&self) -> RepuNFT {
+		RepuNFT::None
+	}
+	/// Members of the team led by the seeker, who get the rewards along with the seeker.
+	/// Only top-tier quests have teams.
+	fn get_team_members(&self, _seeker: ActorId) -> Vec<ActorId> {
+		Vec::new()
+	}
+	/// The grading the seeker got in this quest, if graded.
+	fn get_grading(&self, seeker: ActorId) -> Option<Gradings> {
+		self.get_base().gradings.get(&seeker).cloned().flatten()
+	}
+	/// The provider asks the seeker to revise the submission instead of grading it,
+	/// returns how many more revisions can be requested after this one.
+	fn request_revision(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, feedback: String) -> Result<u8, QuestError> {
+		self.get_base_mut().request_revision(msg_src, commiter, submission, feedback)
+	}
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo, Clone)]
@@ -123,12 +249,21 @@ pub struct IncomingQuest {
 	pub provider_name: String,
 	pub contact_info: String,
 	pub free_gradings: u8,
+	pub stake: u128,
 	pub hiring_for: String,
 	pub skill_tags: SkillNFT,
 	pub reputation_nft: RepuNFT,
-	pub prize: String,
+	pub prize_split: Vec<u8>,
 	pub application_deadline: u32,
 	pub dedicated_to: Option<Vec<ActorId>>,
+	pub max_revisions: u8,
+	pub rubric: Option<Rubric>,
+	pub max_team_size: u8,
+	pub prerequisites: Vec<Prerequisite>,
+	pub milestones: Vec<Milestone>,
+	pub reveal_window: u32,
+	pub passcode_hash: Option<[u8; 32]>,
+	pub passcode_salt: [u8; 32],
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo, Clone)]
@@ -153,16 +288,16 @@ pub struct BaseTierQuest {
 }
 
 impl QuestTrait for BaseTierQuest {
-	fn commit(&mut self, msg_src: ActorId) -> Result<(), String> {
+	fn commit(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
 		if self.base.submissions.contains_key(&msg_src) {
-			return Err(String::from("Already committed to this quest!"));
+			return Err(QuestError::AlreadyCommitted);
 		}
 		self.base.submissions.insert(msg_src, SeekerStatus::Waiting);
 		self.base.gradings.insert(msg_src, None);
 		return Ok(());
 	}
 
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String> {
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError> {
 		if let Err(e) = self.base.submit(msg_src, submission) {
 			return Err(e);
 		} else {
@@ -170,26 +305,31 @@ impl QuestTrait for BaseTierQuest {
 		}
 	}
 
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> Result<(), String> {
-		if let Err(e) = self.base.grade( msg_src, commiter, submission, gradings) {
-			return Err(e);
-		} else {
-			self.base.capacity += 1;
-			return Ok(());
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, gradings: Gradings) -> Result<(), QuestError> {
+		match self.base.grade(msg_src, commiter, submission, gradings) {
+			Ok(true) => {
+				self.base.capacity += 1;
+				return Ok(());
+			},
+			Ok(false) => return Ok(()),
+			Err(e) => return Err(e),
 		}
-		// TODO: implement the issuance of skill tokens after the reputation contract is ready
-        // ....
 	}
 
-	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), String> {
-		if msg_src != self.base.provider {
-            return Err(String::from("Only the quest owner can modify!"));
+	// Nothing is consumed when committing to base-tier quests
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		self.base.withdraw(msg_src)
+	}
+
+	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), QuestError> {
+		if !self.base.is_manager(msg_src) {
+            return Err(QuestError::NotManager);
         }
         if self.base.submissions.len() > 0 {
-            return Err(String::from("Quest can only get modified before any seeker claims it!"));
+            return Err(QuestError::AlreadyClaimed);
         }
         if self.base.modified {
-            return Err(String::from("Quest can only get modified once!"));
+            return Err(QuestError::AlreadyModified);
         }
         self.base.quest_name = base_info.quest_name;
         self.base.description = base_info.description;
@@ -212,6 +352,14 @@ impl QuestTrait for BaseTierQuest {
 	fn get_deadline(&self) -> u64 {
 		self.base.deadline
 	}
+
+	fn get_base(&self) -> &Base {
+		&self.base
+	}
+
+	fn get_base_mut(&mut self) -> &mut Base {
+		&mut self.base
+	}
 }
 
 // Mid Tier - Hiring Purpose Quest
@@ -224,6 +372,9 @@ pub struct MidTierQuest {
 	/// Functional requirements:
 	/// 1. range needs to > MIN_LIMIT.
 	pub free_gradings: u8,
+	/// Specified by the quest providers, how much value seekers need to stake once free gradings run out.
+	/// Staking is disabled if it is set to 0.
+	pub stake: u128,
 	/// Specify the position the provider is hiring for, e.g. Master, Ph.D., Internship.
 	pub hiring_for: String,
 	/// Specify which type of skill NFT is needed to start working on this quest.
@@ -234,33 +385,37 @@ pub struct MidTierQuest {
 }
 
 impl QuestTrait for MidTierQuest {
-	fn commit(&mut self, msg_src: ActorId) -> Result<(), String> {
-		// For mid tier quests, we need to check the following things:
-        // 1) The seeker has the required skill NFT or
-    	// 2) The seeker has enough USDT to stake
+	// The skill NFT of the seeker is verified against the reputation contract before reaching here,
+	// see `check_skill_nft` in the helper functions.
+	fn commit(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		if self.base.submissions.contains_key(&msg_src) {
+			return Err(QuestError::AlreadyCommitted);
+		}
 
 		// Check if there are still free gradings left, if not, staking is required
+		let value = gstd::msg::value();
 		if self.free_gradings > 0 {
-			self.free_gradings -= 1;
-			if helper_functions::check_skill_nft(msg_src, self.skill_tags) {
-				if self.base.submissions.contains_key(&msg_src) {
-					return Err(String::from("Already committed to this quest!"));
-				}
-				self.base.submissions.insert(msg_src, SeekerStatus::Waiting);
-				self.base.gradings.insert(msg_src, None);
-				helper_functions::consume_skill_nft(msg_src, self.skill_tags);
-				self.base.capacity -= 1;
-				return Ok(());
-			} else {
-				return Err(String::from("No skill NFT found!"));
+			if value > 0 {
+				return Err(QuestError::StakeNotRequired);
 			}
+			self.free_gradings -= 1;
 		} else {
-			// TODO: implement proper logic after the staking logic is ready
-			return Err(String::from("No free gradings left!"));
+			if self.stake == 0 {
+				return Err(QuestError::NoFreeGradingsLeft);
+			}
+			if value != self.stake {
+				return Err(QuestError::IncorrectStake);
+			}
+			self.base.stakes.insert(msg_src, value);
 		}
+
+		self.base.submissions.insert(msg_src, SeekerStatus::Waiting);
+		self.base.gradings.insert(msg_src, None);
+		self.base.capacity -= 1;
+		return Ok(());
 	}
 
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String> {
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError> {
 		if let Err(e) = self.base.submit(msg_src, submission) {
 			return Err(e);
 		} else {
@@ -268,26 +423,42 @@ impl QuestTrait for MidTierQuest {
 		}
 	}
 
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> Result<(), String> {
-		if let Err(e) = self.base.grade( msg_src, commiter, submission, gradings) {
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, gradings: Gradings) -> Result<(), QuestError> {
+		match self.base.grade(msg_src, commiter, submission, gradings) {
+			// The seeker keeps the slot until the last milestone is graded
+			Ok(true) => {
+				self.base.capacity += 1;
+				return Ok(());
+			},
+			Ok(false) => return Ok(()),
+			Err(e) => return Err(e),
+		}
+	}
+
+	// The free grading is refunded, while the stake of staked seekers stays escrowed
+	// and is forfeited to the provider at the deadline, the same as abandoning the quest.
+	// The skill NFT stays consumed, so it can't be used to hop between mid-tier quests.
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		if let Err(e) = self.base.withdraw(msg_src) {
 			return Err(e);
 		} else {
+			if !self.base.stakes.contains_key(&msg_src) {
+				self.free_gradings += 1;
+			}
 			self.base.capacity += 1;
 			return Ok(());
 		}
-		// TODO: implement the issuance of skill tokens after the reputation contract is ready
-        // ....
 	}
 
-	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), String> {
-		if msg_src != self.base.provider {
-            return Err(String::from("Only the quest owner can modify!"));
+	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), QuestError> {
+		if !self.base.is_manager(msg_src) {
+            return Err(QuestError::NotManager);
         }
         if self.base.submissions.len() > 0 {
-            return Err(String::from("Quest can only get modified before any seeker claims it!"));
+            return Err(QuestError::AlreadyClaimed);
         }
         if self.base.modified {
-            return Err(String::from("Quest can only get modified once!"));
+            return Err(QuestError::AlreadyModified);
         }
         self.base.quest_name = base_info.quest_name;
         self.base.description = base_info.description;
@@ -310,17 +481,40 @@ impl QuestTrait for MidTierQuest {
 	fn get_deadline(&self) -> u64 {
 		self.base.deadline
 	}
+
+	fn get_base(&self) -> &Base {
+		&self.base
+	}
+
+	fn get_base_mut(&mut self) -> &mut Base {
+		&mut self.base
+	}
+
+	fn get_reputation_nft(&self) -> RepuNFT {
+		self.reputation_nft.clone()
+	}
 }
 
 // Top Tier - Competition Quest
 #[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
 pub struct TopTierQuest {
 	pub base: Base,
-	/// Specified by the competition organizer.
+	/// The prize value attached by the competition organizer when publishing, escrowed by the contract.
 	///
 	/// Security requirements:
 	/// 1. disclaimers need to display to seekers.
-	pub prize: String,
+	pub prize: u128,
+	/// Percentage of the prize for each rank, starting from the 1st place.
+	///
+	/// Functional requirements:
+	/// 1. the percentages must add up to 100.
+	pub prize_split: Vec<u8>,
+	/// Winners ranked by the provider, starting from the 1st place.
+	pub winners: Vec<ActorId>,
+	/// Prize value still held by the contract, i.e. not paid out to winners nor refunded.
+	pub prize_balance: u128,
+	/// Set once the winners are assigned or the prize is refunded, the prize can't be paid out again.
+	pub prize_finalized: bool,
 	/// Specify deadline in the format of Vara block height.
 	/// This deadline is different than the `deadline` field in the base structure,
 	/// here it means after which users are not able to claim this quest anymore.
@@ -331,18 +525,198 @@ pub struct TopTierQuest {
 	/// Specify which reputation will be issued as rewards.
 	/// Notice that there is also an implicit reward - global recognition (fame)
 	pub reputation_nft: RepuNFT,
+	/// Maximum members of a team including the leader, quests with less than 2 are solo only.
+	/// It can't be more than `MAX_TEAM_SIZE`.
+	pub max_team_size: u8,
+	/// Teams formed for the quest: leader -> `Team`
+	pub teams: BTreeMap<ActorId, Team>,
+	/// Blocks after the deadline during which sealed submissions can be revealed.
+	/// Quests with a reveal window only take sealed submissions, so competitors can't copy each other.
+	pub reveal_window: u32,
+}
+
+impl TopTierQuest {
+	/// Find the leader of the team the actor leads or has joined.
+	pub fn team_leader(&self, actor: ActorId) -> Option<ActorId> {
+		self.teams
+			.values()
+			.find(|team| team.leader == actor || team.members.contains(&actor))
+			.map(|team| team.leader)
+	}
+
+	/// The seeker leads a new team, which commits to the quest as one seeker.
+	pub fn create_team(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		if self.max_team_size < 2 {
+			return Err(QuestError::TeamsNotSupported);
+		}
+		if self.team_leader(msg_src).is_some() {
+			return Err(QuestError::AlreadyInTeam);
+		}
+		if self.base.submissions.contains_key(&msg_src) {
+			return Err(QuestError::AlreadyCommitted);
+		}
+
+		self.teams.insert(msg_src, Team { leader: msg_src, ..Default::default() });
+		return Ok(());
+	}
+
+	/// The leader invites a member, invitations count towards the team size until they are accepted.
+	pub fn invite_member(&mut self, msg_src: ActorId, member: ActorId) -> Result<(), QuestError> {
+		if self.team_leader(member).is_some() || self.base.submissions.contains_key(&member) {
+			return Err(QuestError::AlreadyInTeam);
+		}
+		let max_team_size = self.max_team_size as usize;
+		let team = match self.teams.get_mut(&msg_src) {
+			Some(team) => team,
+			None => return Err(QuestError::TeamNotFound),
+		};
+		if team.committed {
+			return Err(QuestError::TeamLocked);
+		}
+		if team.invited.contains(&member) {
+			return Err(QuestError::AlreadyInvited);
+		}
+		if 1 + team.members.len() + team.invited.len() >= max_team_size {
+			return Err(QuestError::TeamFull);
+		}
+
+		team.invited.push(member);
+		return Ok(());
+	}
+
+	/// The invited member joins the team of the leader.
+	pub fn accept_invite(&mut self, msg_src: ActorId, leader: ActorId) -> Result<(), QuestError> {
+		if self.team_leader(msg_src).is_some() || self.base.submissions.contains_key(&msg_src) {
+			return Err(QuestError::AlreadyInTeam);
+		}
+		let team = match self.teams.get_mut(&leader) {
+			Some(team) => team,
+			None => return Err(QuestError::TeamNotFound),
+		};
+		if team.committed {
+			return Err(QuestError::TeamLocked);
+		}
+		if !team.invited.contains(&msg_src) {
+			return Err(QuestError::NotInvited);
+		}
+
+		team.invited.retain(|invited| invited != &msg_src);
+		team.members.push(msg_src);
+		return Ok(());
+	}
+
+	/// Keep the commitment to the seeker's submission until it is revealed after the deadline.
+	pub fn seal(&mut self, msg_src: ActorId, commitment: [u8; 32]) -> Result<(), QuestError> {
+		if self.reveal_window == 0 {
+			return Err(QuestError::SealingNotSupported);
+		}
+		match self.base.submissions.get(&msg_src) {
+			Some(SeekerStatus::Waiting) | Some(SeekerStatus::RevisionRequested { .. }) => {},
+			_ => return Err(QuestError::NotCommitted),
+		}
+		self.base.submissions.insert(msg_src, SeekerStatus::Sealed(commitment));
+		return Ok(());
+	}
+
+	/// Check the revealed submission against the seeker's commitment, it can then be graded.
+	pub fn reveal(&mut self, quest_id: &QuestId, msg_src: ActorId, submission: SubmissionRef, salt: [u8; 32], block_height: u64) -> Result<(), QuestError> {
+		let commitment = match self.base.submissions.get(&msg_src) {
+			Some(SeekerStatus::Sealed(commitment)) => *commitment,
+			_ => return Err(QuestError::NotSealed),
+		};
+		// Reveals only start once no one can seal anymore
+		let deadline = self.base.current_deadline(msg_src);
+		if block_height < deadline || block_height >= deadline + self.reveal_window as u64 {
+			return Err(QuestError::NotInRevealWindow);
+		}
+		if seal_submission(quest_id, msg_src, &submission, &salt) != commitment {
+			return Err(QuestError::CommitmentMismatch);
+		}
+
+		self.base.submission_history.entry(msg_src).or_default().push(submission.clone());
+		self.base.submissions.insert(msg_src, SeekerStatus::Submitted(submission));
+		return Ok(());
+	}
+
+	/// Rank the winners and calculate their payouts according to the prize split.
+	/// Ranks without winners leave their share in the prize balance, which can be refunded later.
+	pub fn assign_winners(&mut self, msg_src: ActorId, winners: Vec<ActorId>) -> Result<Vec<(ActorId, u128)>, QuestError> {
+		if msg_src != self.base.provider {
+			return Err(QuestError::NotProvider);
+		}
+		if self.prize_finalized {
+			return Err(QuestError::PrizeFinalized);
+		}
+		if winners.is_empty() || winners.len() > self.prize_split.len() {
+			return Err(QuestError::InvalidWinners);
+		}
+		for (rank, winner) in winners.iter().enumerate() {
+			if winners[..rank].contains(winner) {
+				return Err(QuestError::InvalidWinners);
+			}
+			match self.base.gradings.get(winner) {
+				Some(Some(Gradings::Accept)) | Some(Some(Gradings::Good)) => {},
+				_ => return Err(QuestError::WinnerNotPassed),
+			}
+		}
+
+		let payouts: Vec<(ActorId, u128)> = winners
+			.iter()
+			.zip(self.prize_split.iter())
+			.map(|(winner, percentage)| (*winner, self.prize * (*percentage as u128) / 100))
+			.collect();
+
+		self.prize_balance -= payouts.iter().map(|(_, amount)| amount).sum::<u128>();
+		self.winners = winners;
+		self.prize_finalized = true;
+
+		return Ok(payouts);
+	}
+
+	/// Block height from which the prize can be refunded without assigning the winners.
+	pub fn prize_refundable_at(&self) -> u64 {
+		self.base.deadline + self.reveal_window as u64 + PRIZE_GRACE_PERIOD
+	}
+
+	/// Take the prize balance that is not paid out to any winner.
+	/// Callers need to make sure the quest is finished.
+	pub fn refund_prize(&mut self, msg_src: ActorId, block_height: u64) -> Result<u128, QuestError> {
+		if msg_src != self.base.provider {
+			return Err(QuestError::NotProvider);
+		}
+		// Winners get the chance to be paid before the provider takes the prize back
+		if !self.prize_finalized && block_height < self.prize_refundable_at() {
+			return Err(QuestError::PrizeNotRefundable);
+		}
+		if self.prize_balance == 0 {
+			return Err(QuestError::NoPrizeLeft);
+		}
+
+		let refund = self.prize_balance;
+		self.prize_balance = 0;
+		self.prize_finalized = true;
+
+		return Ok(refund);
+	}
 }
 
 impl QuestTrait for TopTierQuest {
 	// For top tier quests, we need to check the following things:
     // 1) The application deadline has not passed
-	fn commit(&mut self, msg_src: ActorId) -> Result<(), String> {
+	fn commit(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
 		if self.application_deadline < gstd::exec::block_height() {
-			return Err(String::from("Application deadline has passed!"));
+			return Err(QuestError::DeadlinePassed);
 		}
 
 		if self.base.submissions.contains_key(&msg_src) {
-			return Err(String::from("Already committed to this quest!"));
+			return Err(QuestError::AlreadyCommitted);
+		}
+		// Teams commit through their leader, and no one can join them afterwards
+		if let Some(team) = self.teams.get_mut(&msg_src) {
+			team.committed = true;
+			team.invited.clear();
+		} else if self.team_leader(msg_src).is_some() {
+			return Err(QuestError::InTeam);
 		}
 		self.base.submissions.insert(msg_src, SeekerStatus::Waiting);
 		self.base.gradings.insert(msg_src, None);
@@ -351,7 +725,10 @@ impl QuestTrait for TopTierQuest {
 		return Ok(());
 	}
 
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String> {
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError> {
+		if self.reveal_window > 0 {
+			return Err(QuestError::SealRequired);
+		}
 		if let Err(e) = self.base.submit(msg_src, submission) {
 			return Err(e);
 		} else {
@@ -359,26 +736,37 @@ impl QuestTrait for TopTierQuest {
 		}
 	}
 
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> Result<(), String> {
-		if let Err(e) = self.base.grade( msg_src, commiter, submission, gradings) {
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, gradings: Gradings) -> Result<(), QuestError> {
+		match self.base.grade(msg_src, commiter, submission, gradings) {
+			// The seeker keeps the slot until the last milestone is graded
+			Ok(true) => {
+				self.base.capacity += 1;
+				return Ok(());
+			},
+			Ok(false) => return Ok(()),
+			Err(e) => return Err(e),
+		}
+	}
+
+	// Only the slot is released, top-tier quests don't consume anything from seekers
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		if let Err(e) = self.base.withdraw(msg_src) {
 			return Err(e);
 		} else {
 			self.base.capacity += 1;
 			return Ok(());
 		}
-		// TODO: implement the issuance of skill tokens after the reputation contract is ready
-    	// ....
 	}
 
-	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), String> {
-		if msg_src != self.base.provider {
-            return Err(String::from("Only the quest owner can modify!"));
+	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), QuestError> {
+		if !self.base.is_manager(msg_src) {
+            return Err(QuestError::NotManager);
         }
         if self.base.submissions.len() > 0 {
-            return Err(String::from("Quest can only get modified before any seeker claims it!"));
+            return Err(QuestError::AlreadyClaimed);
         }
         if self.base.modified {
-            return Err(String::from("Quest can only get modified once!"));
+            return Err(QuestError::AlreadyModified);
         }
         self.base.quest_name = base_info.quest_name;
         self.base.description = base_info.description;
@@ -401,6 +789,36 @@ impl QuestTrait for TopTierQuest {
 	fn get_deadline(&self) -> u64 {
 		self.base.deadline
 	}
+
+	fn get_base(&self) -> &Base {
+		&self.base
+	}
+
+	fn get_base_mut(&mut self) -> &mut Base {
+		&mut self.base
+	}
+
+	fn get_reputation_nft(&self) -> RepuNFT {
+		self.reputation_nft.clone()
+	}
+
+	fn get_team_members(&self, seeker: ActorId) -> Vec<ActorId> {
+		self.teams.get(&seeker).map_or(Vec::new(), |team| team.members.clone())
+	}
+
+	// Team members are graded through their leader
+	fn get_grading(&self, seeker: ActorId) -> Option<Gradings> {
+		let seeker = self.team_leader(seeker).unwrap_or(seeker);
+		self.base.gradings.get(&seeker).cloned().flatten()
+	}
+
+	// Sealed submissions are revealed after their deadline, so a revision could never be sealed again
+	fn request_revision(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, feedback: String) -> Result<u8, QuestError> {
+		if self.reveal_window > 0 {
+			return Err(QuestError::RevisionsNotSupported);
+		}
+		self.base.request_revision(msg_src, commiter, submission, feedback)
+	}
 }
 
 // Dedicated Quest
@@ -409,22 +827,39 @@ pub struct DedicatedQuest {
 	pub base: Base,
 	/// Specify the wallet addresses that can claim this quest.
 	///
-	/// If this part is left empty, it means anyone knows the passcodes can claim this quest,
-	/// suitable for the usage by providers who can't gather all the wallet addressess needed upfront, e.g. online courses and etc.
+	/// If this part is left empty, anyone can claim this quest, or anyone who knows the passcode if `passcode_hash` is set.
 	pub dedicated_to: Option<Vec<ActorId>>,
+	/// Salted hash of the passcode seekers need to claim this quest, see `helper_functions::hash_passcode`.
+	///
+	/// Suitable for the usage by providers who can't gather all the wallet addressess needed upfront, e.g. online courses and etc.
+	/// Notice that the passcode is sent in plain text with the commit message, so it only gates who can claim the quest.
+	pub passcode_hash: Option<[u8; 32]>,
+	/// Salt chosen by the provider, the passcode is hashed along with it.
+	pub passcode_salt: [u8; 32],
+}
+
+impl DedicatedQuest {
+	/// Quests without a passcode can be claimed without one.
+	pub fn check_passcode(&self, passcode: Option<&str>) -> bool {
+		match (&self.passcode_hash, passcode) {
+			(None, _) => true,
+			(Some(passcode_hash), Some(passcode)) => &hash_passcode(&self.passcode_salt, passcode) == passcode_hash,
+			(Some(_), None) => false,
+		}
+	}
 }
 
 impl QuestTrait for DedicatedQuest {
 	// For dedicated quests, we need to check the following things:
     // 1) The seeker is in the dedicated list
-	fn commit(&mut self, msg_src: ActorId) -> Result<(), String> {
+	fn commit(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
 		if self.dedicated_to.is_some() {
 			if !self.dedicated_to.as_ref().unwrap().contains(&msg_src) {
-				return Err(String::from("You are not on the dedication list!"));
+				return Err(QuestError::NotDedicated);
 			}
 		}
 		if self.base.submissions.contains_key(&msg_src) {
-			return Err(String::from("Already committed to this quest!"));
+			return Err(QuestError::AlreadyCommitted);
 		}
 		self.base.submissions.insert(msg_src, SeekerStatus::Waiting);
 		self.base.gradings.insert(msg_src, None);
@@ -432,7 +867,7 @@ impl QuestTrait for DedicatedQuest {
 		return Ok(());
 	}
 
-	fn submit(&mut self, msg_src: ActorId, submission: Submmision) -> Result<(), String> {
+	fn submit(&mut self, msg_src: ActorId, submission: SubmissionRef) -> Result<(), QuestError> {
 		if let Err(e) = self.base.submit(msg_src, submission) {
 			return Err(e);
 		} else {
@@ -440,26 +875,31 @@ impl QuestTrait for DedicatedQuest {
 		}
 	}
 
-	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> Result<(), String> {
-		if let Err(e) = self.base.grade( msg_src, commiter, submission, gradings) {
-			return Err(e);
-		} else {
-			self.base.capacity += 1;
-			return Ok(());
+	fn grade(&mut self, msg_src: ActorId, commiter: ActorId, submission: SubmissionRef, gradings: Gradings) -> Result<(), QuestError> {
+		match self.base.grade(msg_src, commiter, submission, gradings) {
+			Ok(true) => {
+				self.base.capacity += 1;
+				return Ok(());
+			},
+			Ok(false) => return Ok(()),
+			Err(e) => return Err(e),
 		}
-		// TODO: implement the issuance of skill tokens after the reputation contract is ready
-        // ....
 	}
 
-	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), String> {
-		if msg_src != self.base.provider {
-            return Err(String::from("Only the quest owner can modify!"));
+	// Nothing is consumed when committing to dedicated quests
+	fn withdraw(&mut self, msg_src: ActorId) -> Result<(), QuestError> {
+		self.base.withdraw(msg_src)
+	}
+
+	fn modify(&mut self, msg_src: ActorId, base_info: Modifiable) -> Result<(), QuestError> {
+		if !self.base.is_manager(msg_src) {
+            return Err(QuestError::NotManager);
         }
         if self.base.submissions.len() > 0 {
-            return Err(String::from("Quest can only get modified before any seeker claims it!"));
+            return Err(QuestError::AlreadyClaimed);
         }
         if self.base.modified {
-            return Err(String::from("Quest can only get modified once!"));
+            return Err(QuestError::AlreadyModified);
         }
         self.base.quest_name = base_info.quest_name;
         self.base.description = base_info.description;
@@ -482,6 +922,55 @@ impl QuestTrait for DedicatedQuest {
 	fn get_deadline(&self) -> u64 {
 		self.base.deadline
 	}
+
+	fn get_base(&self) -> &Base {
+		&self.base
+	}
+
+	fn get_base_mut(&mut self) -> &mut Base {
+		&mut self.base
+	}
+}
+
+/// Seekers who commit to a top-tier quest together, represented by the leader.
+/// The leader commits and submits for the team, and every member gets the rewards.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Team {
+	pub leader: ActorId,
+	/// Members who accepted the invitation, not including the leader.
+	pub members: Vec<ActorId>,
+	pub invited: Vec<ActorId>,
+	/// Set once the leader commits, no one can join afterwards.
+	pub committed: bool,
+}
+
+/// A quest of any tier, tagged with its tier, returned by the state queries.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum AnyQuest {
+	BaseTier(BaseTierQuest),
+	MidTier(MidTierQuest),
+	TopTier(TopTierQuest),
+	Dedicated(DedicatedQuest),
+}
+
+impl AnyQuest {
+	pub fn get_base(&self) -> &Base {
+		match self {
+			AnyQuest::BaseTier(quest) => &quest.base,
+			AnyQuest::MidTier(quest) => &quest.base,
+			AnyQuest::TopTier(quest) => &quest.base,
+			AnyQuest::Dedicated(quest) => &quest.base,
+		}
+	}
+
+	pub fn get_quest_type(&self) -> QuestType {
+		match self {
+			AnyQuest::BaseTier(_) => QuestType::BaseTier,
+			AnyQuest::MidTier(_) => QuestType::MidTier,
+			AnyQuest::TopTier(_) => QuestType::TopTier,
+			AnyQuest::Dedicated(_) => QuestType::Dedicated,
+		}
+	}
 }
 
 /// The status of a seeker for a quest.
@@ -489,8 +978,39 @@ impl QuestTrait for DedicatedQuest {
 pub enum SeekerStatus {
 	#[default]
 	Waiting,
-	Submitted(Submmision),
+	/// Submitted the commitment to a submission of a commit-reveal quest, see `TopTierQuest::reveal_window`.
+	Sealed([u8; 32]),
+	Submitted(SubmissionRef),
+	/// The provider asked for changes, the seeker can submit again.
+	RevisionRequested {
+		feedback: String,
+		/// Revisions the provider can still request after the next submission.
+		remaining_rounds: u8,
+	},
+	/// Graded as passed, but the reputation contract has not confirmed all rewards yet.
+	GradedPendingReward(Gradings),
 	Graded(Gradings),
+	/// Withdrew from the quest before submitting.
+	Withdrawn,
+}
+
+/// Rewards issued by the reputation contract to seekers who passed a quest.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum Reward {
+	/// Skill FT of the given token id in the reputation contract.
+	SkillToken(TokenId),
+	/// Reputation NFT of the quest, with the graded submission attached.
+	ReputationNft(SubmissionRef),
+}
+
+/// A reward message sent to the reputation contract that has not been confirmed yet.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct PendingReward {
+	pub quest_id: QuestId,
+	pub seeker: ActorId,
+	pub reward: Reward,
+	/// Set when the reputation contract failed to issue the reward, it can be sent again with `RetryRewards`.
+	pub failed: bool,
 }
 
 /// Possible gradings for every quest.
@@ -502,9 +1022,148 @@ pub enum Gradings {
 	Reject,
 }
 
+impl Gradings {
+	fn rank(&self) -> u8 {
+		match self {
+			Gradings::Reject => 0,
+			Gradings::Accept => 1,
+			Gradings::Good => 2,
+		}
+	}
+
+	/// Whether the grading is at least as good as the minimum.
+	pub fn meets(&self, minimum: &Gradings) -> bool {
+		self.rank() >= minimum.rank()
+	}
+}
+
+/// A quest that must be passed before committing to another one.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Prerequisite {
+	pub quest_id: QuestId,
+	/// The minimum grading the seeker must have got in the quest.
+	pub min_grading: Gradings,
+}
+
+/// A stage of a quest with its own deliverables and deadline.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Milestone {
+	pub deliverables: String,
+	/// Specify deadline in the format of Vara block height.
+	pub deadline: u64,
+}
+
+/// A named criterion of a rubric.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Criterion {
+	pub name: String,
+	/// Relative weight of the criterion in the overall score.
+	pub weight: u8,
+	pub max_score: u8,
+}
+
+/// Criteria declared by the provider at publish time, the weighted overall score maps onto `Gradings`.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Rubric {
+	pub criteria: Vec<Criterion>,
+	/// Minimum overall score in percent to get `Gradings::Accept`.
+	pub pass_threshold: u8,
+	/// Minimum overall score in percent to get `Gradings::Good`.
+	pub good_threshold: u8,
+}
+
+impl Rubric {
+	/// Every criterion needs a weight and a max score, and the thresholds must be ordered within 100%.
+	pub fn is_valid(&self) -> bool {
+		!self.criteria.is_empty()
+			&& self.criteria.iter().all(|criterion| criterion.weight > 0 && criterion.max_score > 0)
+			&& self.pass_threshold <= self.good_threshold
+			&& self.good_threshold <= 100
+	}
+
+	/// Weighted overall score in basis points, one score per criterion in the order of the rubric.
+	pub fn overall_score(&self, scores: &[u8]) -> Result<u32, QuestError> {
+		if scores.len() != self.criteria.len() {
+			return Err(QuestError::InvalidScores);
+		}
+
+		let mut weighted: u64 = 0;
+		let mut total_weight: u64 = 0;
+		for (criterion, score) in self.criteria.iter().zip(scores) {
+			if score > &criterion.max_score {
+				return Err(QuestError::InvalidScores);
+			}
+			weighted += criterion.weight as u64 * *score as u64 * 10_000 / criterion.max_score as u64;
+			total_weight += criterion.weight as u64;
+		}
+
+		Ok((weighted / total_weight) as u32)
+	}
+
+	pub fn gradings(&self, overall_score: u32) -> Gradings {
+		if overall_score >= self.good_threshold as u32 * 100 {
+			Gradings::Good
+		} else if overall_score >= self.pass_threshold as u32 * 100 {
+			Gradings::Accept
+		} else {
+			Gradings::Reject
+		}
+	}
+}
+
+/// How the provider grades a submission.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum Evaluation {
+	/// For quests without a rubric.
+	Gradings(Gradings),
+	/// For quests with a rubric, one score per criterion.
+	Scores(Vec<u8>),
+}
+
+/// The scores and feedback a seeker got with the grade.
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct Assessment {
+	/// Empty if the quest has no rubric.
+	pub scores: Vec<u8>,
+	/// Weighted overall score in basis points, `None` if the quest has no rubric.
+	pub overall_score: Option<u32>,
+	pub feedback: String,
+	/// Block height of the grade, appeals are open for a window after it.
+	pub graded_at: u32,
+	/// The provider or grader who made the grade.
+	pub grader: ActorId,
+}
+
+/// A seeker's appeal against a rejection.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct Appeal {
+	pub reason: String,
+	/// Block height the appeal was filed at.
+	pub filed_at: u32,
+	pub status: AppealStatus,
+}
+
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub enum AppealStatus {
+	#[default]
+	Pending,
+	/// The rejection stands.
+	Upheld,
+	/// The rejection was replaced by the given gradings, and the rewards were issued.
+	Overturned(Gradings),
+}
+
+/// Decision of the admin or an arbiter on an appeal.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum AppealDecision {
+	Uphold,
+	/// Replace the rejection with a passing grade.
+	Overturn(Gradings),
+}
+
 /// List all possible skill tokens we support.
 /// This list should be manageable through OpenGov.
-#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SkillToken {
 	#[default]
 	None,
@@ -521,6 +1180,16 @@ pub enum SkillNFT {
 	Simulation,
 }
 
+impl SkillNFT {
+	/// The skill NFT name registered in the reputation contract.
+	pub fn name(&self) -> String {
+		match self {
+			SkillNFT::Python => String::from("Python"),
+			SkillNFT::Simulation => String::from("Simulation"),
+		}
+	}
+}
+
 /// List all possible reputation nfts we can provide, this should be more generall then the skill nfts.
 /// This list should be manageable through OpenGov, but preferabily with a faster voting process setup.
 #[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
@@ -532,6 +1201,18 @@ pub enum RepuNFT {
 	CSInternship,
 }
 
+impl RepuNFT {
+	/// The skill NFT name registered in the reputation contract, `None` means nothing to issue.
+	pub fn name(&self) -> Option<String> {
+		match self {
+			RepuNFT::None => None,
+			RepuNFT::CSHackathonWinner => Some(String::from("CSHackathonWinner")),
+			RepuNFT::ResearchCompetitionWinner => Some(String::from("ResearchCompetitionWinner")),
+			RepuNFT::CSInternship => Some(String::from("CSInternship")),
+		}
+	}
+}
+
 #[derive(Debug, Decode, Encode, TypeInfo, Default, Clone, PartialEq, Eq)]
 pub enum QuestStatus {
 	#[default]
@@ -558,6 +1239,7 @@ pub enum QuestAction {
 	/// Requirements:
 	/// * The msg sender must be an approved recruiter.
 	/// * All fields of a quest must be filled.
+	/// * Top-tier quests must carry the prize value, other quests must not carry any value.
 	/// 
 	/// Arguments:
 	/// * quest_type: the type of the quest to be published.
@@ -570,11 +1252,20 @@ pub enum QuestAction {
 	/// Requirements:
 	/// * the quest of the given id must exist.
 	/// * the quest must be open.
+	/// * for mid-tier quests, the seeker must hold the given skill NFT in the reputation contract,
+	///   and that skill NFT must not have been used to claim another mid-tier quest.
+	/// * for mid-tier quests without free gradings left, the message must carry exactly the quest's stake.
+	///   Value attached to any other commit is sent back.
+	/// * for dedicated quests with a passcode, the passcode must hash to the quest's passcode hash along with its salt.
 	/// 
 	/// Arguments:
 	/// * quest_id: the id of the quest to be claimed.
+	/// * skill_nft: the id of the skill NFT used to claim a mid-tier quest, ignored by other tiers.
+	/// * passcode: the passcode handed out by the provider of a dedicated quest, ignored by other quests.
 	Commit {
 		quest_id: QuestId,
+		skill_nft: Option<TokenId>,
+		passcode: Option<String>,
 	},
 	/// Seekers submit their submissions.
 	/// 
@@ -582,20 +1273,38 @@ pub enum QuestAction {
 	/// * the quest of the given id must exist.
 	/// * the quest must NOT be closed.
 	/// * the seeker must have claimed the quest.
+	/// * the submission locator must be well-formed for its storage, with the content hash and size set.
 	/// 
 	/// Arguments:
 	/// * quest_id: the id of the quest to be claimed.
 	/// * submission: the submission of the seeker.
 	Submit {
 		quest_id: QuestId,
-		submission: Submmision,
+		submission: SubmissionRef,
 	},
 	
+	/// Providers grade a submission, with scores if the quest has a rubric.
+	/// For quests with milestones, passing any milestone but the last one moves the seeker on to the next milestone,
+	/// and the rewards are issued once, when the last milestone is passed.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist.
+	/// * the msg sender must be the quest provider or an assigned grader.
+	/// * the submission must be the latest submission of the seeker, and not graded yet.
+	/// * quests with a rubric must be graded by scores, one per criterion within its max score.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * commiter: the seeker who submitted.
+	/// * submission: the submission to be graded.
+	/// * grading: the gradings, or the scores that are mapped onto gradings by the rubric.
+	/// * feedback: explanation of the grade for the seeker.
 	Grade {
 		quest_id: QuestId,
 		commiter: ActorId,
-		submission: Submmision,
-		grading: Gradings,
+		submission: SubmissionRef,
+		grading: Evaluation,
+		feedback: String,
 	},
 	Close {
 		quest_id: QuestId,
@@ -604,16 +1313,571 @@ pub enum QuestAction {
 		quest_id: QuestId,
 		base_info: Modifiable,
 	},
+	/// Seekers leave a quest they committed to but have not submitted to yet.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist and must NOT be finished.
+	/// * the seeker must have committed to the quest and not submitted yet.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest to withdraw from.
+	/// 
+	/// The slot is released for mid-tier and top-tier quests, and a full quest opens again.
+	/// Mid-tier quests refund the free grading, but the skill NFT stays consumed.
+	/// Stakes are not refunded, withdrawing forfeits them to the provider at the deadline.
+	Withdraw {
+		quest_id: QuestId,
+	},
+	/// Providers ask a seeker to revise the submission instead of grading it.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist.
+	/// * the msg sender must be the quest provider or an assigned grader.
+	/// * the submission must be the latest submission of the seeker, and not graded yet.
+	/// * the seeker must have revisions left, see `IncomingQuest::max_revisions`.
+	/// * the quest must not take sealed submissions.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * commiter: the seeker who submitted.
+	/// * submission: the submission to be revised.
+	/// * feedback: what the seeker needs to change.
+	RequestRevision {
+		quest_id: QuestId,
+		commiter: ActorId,
+		submission: SubmissionRef,
+		feedback: String,
+	},
+	/// Resend the rewards the reputation contract failed to issue to a seeker.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist.
+	/// * the msg sender must be the seeker or the quest provider.
+	/// * the seeker must have failed rewards in this quest.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the graded quest.
+	/// * seeker: the seeker who passed the quest.
+	RetryRewards {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	/// Sent by the quest contract to itself as a delayed message when a quest deadline is reached.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the quest contract itself.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * deadline: which deadline of the quest is reached.
+	ExpireDeadline {
+		quest_id: QuestId,
+		deadline: Deadline,
+	},
+	/// Providers rank the winners of a top-tier quest, and the prize is paid out accordingly.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must be a top-tier quest.
+	/// * the msg sender must be the quest provider.
+	/// * winners must have passed the quest, and can't be more than the ranks in the prize split.
+	/// * the prize must not have been finalized.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * winners: the winners, starting from the 1st place.
+	AssignWinners {
+		quest_id: QuestId,
+		winners: Vec<ActorId>,
+	},
+	/// Providers take back the prize that is not paid out to any winner.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must be a top-tier quest.
+	/// * the quest must be finished.
+	/// * the winners must be assigned, or `PRIZE_GRACE_PERIOD` must have passed after the deadline and the reveal window.
+	/// * the msg sender must be the quest provider.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	RefundPrize {
+		quest_id: QuestId,
+	},
+	/// Claim the value that could not be sent to the msg sender, such as a prize, a stake or a refund.
+	/// 
+	/// Requirements:
+	/// * some value must be kept for the msg sender.
+	/// 
+	/// On success returns `QuestEvent::PayoutClaimed`.
+	ClaimPayout,
+	/// Admin approves a new provider to publish quests.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the admin.
+	/// * the provider must not be approved already.
+	/// 
+	/// On success returns `QuestEvent::ProviderAdded`.
+	AddProvider {
+		provider: ActorId,
+	},
+	/// Admin revokes the approval of a provider.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the admin.
+	/// * the provider must be approved.
+	/// 
+	/// Arguments:
+	/// * provider: the provider to be removed.
+	/// * freeze_quests: `true` closes the provider's open and full quests, so no more seekers can commit,
+	///   `false` leaves them running. Committed seekers can always submit and get graded.
+	/// 
+	/// On success returns `QuestEvent::ProviderRemoved`.
+	RemoveProvider {
+		provider: ActorId,
+		freeze_quests: bool,
+	},
+	/// Admin lists all approved providers.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the admin.
+	/// 
+	/// On success returns `QuestEvent::Providers`.
+	ListProviders,
+	/// Seekers appeal against the rejection of their submission.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist.
+	/// * the seeker must have been graded `Gradings::Reject` within the appeal window.
+	/// * the seeker can appeal only once per quest.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * reason: why the seeker thinks the grade is unfair.
+	Appeal {
+		quest_id: QuestId,
+		reason: String,
+	},
+	/// The admin or an arbiter decides on a pending appeal.
+	/// Overturning issues the rewards of the quest, the same as grading does.
+	/// An overturned milestone before the last one moves the seeker on to the next milestone instead.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the admin or an arbiter.
+	/// * the appeal must be pending.
+	/// * overturning must replace the rejection with a passing grade.
+	/// * overturning a milestone before the last one needs the slot of the seeker to be free still.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * seeker: the seeker who appealed.
+	/// * decision: uphold the rejection or overturn it.
+	ResolveAppeal {
+		quest_id: QuestId,
+		seeker: ActorId,
+		decision: AppealDecision,
+	},
+	/// Providers assign the graders and co-managers of their quest, replacing the previous ones.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must exist.
+	/// * the msg sender must be the quest provider.
+	/// 
+	/// Arguments:
+	/// * quest_id: the id of the quest.
+	/// * graders: can grade and request revisions.
+	/// * managers: can modify and close the quest.
+	SetQuestRoles {
+		quest_id: QuestId,
+		graders: Vec<ActorId>,
+		managers: Vec<ActorId>,
+	},
+	/// Seekers lead a new team for a top-tier quest.
+	/// 
+	/// Requirements:
+	/// * the quest of the given id must be an open top-tier quest that allows teams.
+	/// * the seeker must not be in a team of the quest, nor have committed to it.
+	CreateTeam {
+		quest_id: QuestId,
+	},
+	/// Team leaders invite a member before committing.
+	/// 
+	/// Requirements:
+	/// * the msg sender must lead a team of the quest that has not committed yet.
+	/// * the team including the invitations must be smaller than the max team size.
+	/// * the member must not be in a team of the quest, nor have committed to it.
+	InviteToTeam {
+		quest_id: QuestId,
+		member: ActorId,
+	},
+	/// Invited seekers join the team.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be invited by the leader, whose team has not committed yet.
+	/// * the msg sender must have passed the prerequisites of the quest, the same as committing requires.
+	AcceptInvite {
+		quest_id: QuestId,
+		leader: ActorId,
+	},
+	/// Admin replaces the arbiters who can resolve appeals.
+	/// 
+	/// Requirements:
+	/// * the msg sender must be the admin.
+	SetArbiters {
+		arbiters: Vec<ActorId>,
+	},
+	/// Seekers of commit-reveal quests submit the commitment to their submission,
+	/// computed by `helper_functions::seal_submission`.
+	/// 
+	/// Requirements:
+	/// * the quest must be a top-tier quest with a reveal window.
+	/// * the seeker must have committed and not submitted yet.
+	/// * the deadline must not have passed.
+	SealSubmission {
+		quest_id: QuestId,
+		commitment: [u8; 32],
+	},
+	/// Seekers reveal their sealed submission after the deadline, only then it can be graded.
+	/// 
+	/// Requirements:
+	/// * the submission and the salt must match the commitment of the seeker.
+	/// * the reveal window that starts at the deadline must still be open.
+	RevealSubmission {
+		quest_id: QuestId,
+		submission: SubmissionRef,
+		salt: [u8; 32],
+	},
 }
 
-#[derive(Encode, Decode, TypeInfo)]
+/// Deadlines of a quest that are enforced by delayed messages.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum Deadline {
+	/// `TopTierQuest::application_deadline`, the quest gets `Closed` for new commits.
+	Application,
+	/// `Base::deadline`, the quest gets `Finished` and no more submissions are accepted.
+	Final,
+}
+
+/// Events are replied to the sender and broadcast to `ActorId::zero()`,
+/// so off-chain indexers can follow the lifecycle of quests.
+#[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum QuestEvent {
-	Ok {
-		msg: String,
+	/// Replied once the contract is initialized, it is not broadcast.
+	Initialized,
+	Published {
+		quest_id: QuestId,
+		quest_type: QuestType,
+		provider: ActorId,
+	},
+	Committed {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	Submitted {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	Graded {
+		quest_id: QuestId,
+		seeker: ActorId,
+		grading: Gradings,
+	},
+	/// The seeker passed a milestone before the last one and moves on to the next.
+	MilestonePassed {
+		quest_id: QuestId,
+		seeker: ActorId,
+		milestone: u8,
+		grading: Gradings,
+	},
+	/// Closed by the provider, at the application deadline or by freezing the provider's quests.
+	Closed {
+		quest_id: QuestId,
+	},
+	Modified {
+		quest_id: QuestId,
+	},
+	Withdrawn {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	RevisionRequested {
+		quest_id: QuestId,
+		seeker: ActorId,
+		feedback: String,
+		remaining_rounds: u8,
+	},
+	/// The final deadline has passed.
+	Finished {
+		quest_id: QuestId,
+	},
+	/// Sent to the seeker along with the stake once the submission is made.
+	StakeRefunded {
+		quest_id: QuestId,
+		seeker: ActorId,
+		amount: u128,
+	},
+	/// Sent to the provider along with the stakes of seekers who did not submit before the deadline.
+	StakesForfeited {
+		quest_id: QuestId,
+		provider: ActorId,
+		amount: u128,
+	},
+	/// The reputation contract confirmed all rewards of the seeker.
+	RewardsIssued {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	RewardsResent {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	/// Winners in rank order with the amount paid to each of them.
+	WinnersAssigned {
+		quest_id: QuestId,
+		payouts: Vec<(ActorId, u128)>,
+	},
+	/// Sent to the winner along with the prize.
+	PrizePaid {
+		quest_id: QuestId,
+		winner: ActorId,
+		amount: u128,
+	},
+	/// Sent to the provider along with the prize that is not paid out.
+	PrizeRefunded {
+		quest_id: QuestId,
+		provider: ActorId,
+		amount: u128,
+	},
+	/// The value could not be sent, it is kept for the recipient to claim.
+	PayoutFailed {
+		recipient: ActorId,
+		amount: u128,
+	},
+	PayoutClaimed {
+		recipient: ActorId,
+		amount: u128,
 	},
-	Err {
-		msg: String,
+	ProviderAdded {
+		provider: ActorId,
 	},
+	ProviderRemoved {
+		provider: ActorId,
+		/// Quests closed because of the removal, empty if the quests are left running.
+		frozen_quests: Vec<QuestId>,
+	},
+	Providers {
+		providers: Vec<ActorId>,
+	},
+	Appealed {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	AppealResolved {
+		quest_id: QuestId,
+		seeker: ActorId,
+		status: AppealStatus,
+	},
+	ArbitersSet {
+		arbiters: Vec<ActorId>,
+	},
+	QuestRolesSet {
+		quest_id: QuestId,
+		graders: Vec<ActorId>,
+		managers: Vec<ActorId>,
+	},
+	TeamCreated {
+		quest_id: QuestId,
+		leader: ActorId,
+	},
+	TeamInvited {
+		quest_id: QuestId,
+		leader: ActorId,
+		member: ActorId,
+	},
+	TeamJoined {
+		quest_id: QuestId,
+		leader: ActorId,
+		member: ActorId,
+	},
+	Sealed {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+	Revealed {
+		quest_id: QuestId,
+		seeker: ActorId,
+	},
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum QuestError {
+	/// No quest exists with the given id, or it is not of the required tier.
+	QuestNotFound,
+	/// The quest is closed or finished, so no more seekers can commit.
+	QuestNotOpen,
+	/// The quest has reached its capacity.
+	CapacityFull,
+	QuestClosed,
+	QuestFinished,
+	/// The action needs the quest to be finished first.
+	QuestNotFinished,
+	NotAdmin,
+	/// Only the quest provider can perform the action.
+	NotProvider,
+	/// The msg sender is neither the provider nor an assigned grader of the quest.
+	NotGrader,
+	/// The msg sender is neither the provider nor an assigned co-manager of the quest.
+	NotManager,
+	NotApprovedProvider,
+	ProviderAlreadyApproved,
+	/// The msg sender is neither the seeker nor the provider, or not the quest contract for `ExpireDeadline`.
+	Unauthorized,
+	AlreadyCommitted,
+	NotCommitted,
+	NotSubmitted,
+	/// The submission locator is malformed, or its content hash or size is missing.
+	InvalidSubmission,
+	/// The seeker can't withdraw after submitting.
+	AlreadySubmitted,
+	/// The provider has requested all the revisions allowed by the quest.
+	NoRevisionsLeft,
+	/// Quests with a reveal window take sealed submissions, which can't be revised.
+	RevisionsNotSupported,
+	/// A criterion has no weight or max score, or the thresholds are out of order.
+	InvalidRubric,
+	/// The quest has a rubric, so it must be graded by scores.
+	ScoresRequired,
+	/// The quest has no rubric, so it can't be graded by scores.
+	ScoresNotAccepted,
+	/// The scores don't match the criteria, or exceed their max scores.
+	InvalidScores,
+	/// The quest already has seekers, so it can't be modified anymore.
+	AlreadyClaimed,
+	AlreadyModified,
+	/// The seeker is not on the dedication list of a dedicated quest.
+	NotDedicated,
+	/// The deadline of the quest or its application has passed.
+	DeadlinePassed,
+	/// A deadline given when publishing or modifying is not in the future, or too far away to be scheduled,
+	/// or the application deadline of a top-tier quest is later than its deadline.
+	InvalidDeadline,
+	NotEnoughFreeGradings,
+	SkillNftRequired,
+	/// The seeker doesn't hold the skill NFT, or it is not of the skill the quest requires.
+	SkillNftNotFound,
+	SkillNftConsumed,
+	NoFreeGradingsLeft,
+	StakeNotRequired,
+	/// Only mid-tier quests can be staked for.
+	StakeNotAccepted,
+	IncorrectStake,
+	PrizeRequired,
+	/// Only top-tier quests carry a prize.
+	PrizeNotAccepted,
+	InvalidPrizeSplit,
+	PrizeFinalized,
+	/// Winners are duplicated or more than the ranks in the prize split.
+	InvalidWinners,
+	WinnerNotPassed,
+	NoPrizeLeft,
+	/// The winners are not assigned yet and the grace period after the deadline has not passed.
+	PrizeNotRefundable,
+	/// No value is kept for the msg sender.
+	NothingToClaim,
+	/// The value could not be sent, it is still kept for the msg sender.
+	PayoutFailed,
+	NoFailedRewards,
+	/// Only rejected gradings can be appealed.
+	NotRejected,
+	AppealWindowClosed,
+	AlreadyAppealed,
+	AppealNotFound,
+	/// The appeal has been resolved already.
+	AppealNotPending,
+	/// The msg sender is neither the admin nor an arbiter.
+	NotArbiter,
+	/// An appeal can only be overturned to a passing grade.
+	InvalidOverturn,
+	/// Only top-tier quests with a max team size of at least 2 allow teams.
+	TeamsNotSupported,
+	/// The max team size is more than `MAX_TEAM_SIZE`.
+	TeamTooLarge,
+	AlreadyInTeam,
+	TeamNotFound,
+	/// The team has committed, no one can join anymore.
+	TeamLocked,
+	TeamFull,
+	AlreadyInvited,
+	NotInvited,
+	/// Team members commit through their leader.
+	InTeam,
+	/// A prerequisite refers to a quest that does not exist.
+	InvalidPrerequisite,
+	/// The seeker has not passed a prerequisite with the minimum grading.
+	PrerequisiteNotMet,
+	/// Milestone deadlines must be in the future, increasing and no later than the quest deadline,
+	/// and there can't be more than `MAX_MILESTONES` of them.
+	InvalidMilestones,
+	/// Only top-tier quests with a reveal window take sealed submissions.
+	SealingNotSupported,
+	/// Quests with a reveal window only take sealed submissions.
+	SealRequired,
+	NotSealed,
+	/// Sealed submissions are revealed after the deadline, within the reveal window.
+	NotInRevealWindow,
+	/// The revealed submission and salt don't hash to the commitment.
+	CommitmentMismatch,
+	/// Only dedicated quests can be protected by a passcode.
+	PasscodeNotSupported,
+	/// The passcode is missing or doesn't hash to the quest's passcode hash.
+	IncorrectPasscode,
+}
+
+/// Filters of the quest listing query, every filter left as `None` matches all quests.
+#[derive(Debug, Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct QuestFilter {
+	pub status: Option<QuestStatus>,
+	pub quest_type: Option<QuestType>,
+	pub provider: Option<ActorId>,
+	pub institution_name: Option<String>,
+	pub skill_token_name: Option<SkillToken>,
+	/// Quests with deadlines before this block height are left out.
+	pub deadline_from: Option<u64>,
+	/// Quests with deadlines after this block height are left out.
+	pub deadline_to: Option<u64>,
+}
+
+/// Ordering of the quest listing query, both are ascending.
+#[derive(Debug, Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub enum QuestOrder {
+	/// The order quests were published in.
+	#[default]
+	Creation,
+	/// Quests with the earliest deadline first, quests with the same deadline in creation order.
+	Deadline,
+}
+
+/// A page of quests matching the filter.
+#[derive(Debug, Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq)]
+pub struct QuestQuery {
+	pub filter: QuestFilter,
+	pub order: QuestOrder,
+	/// Number of matching quests to skip.
+	pub offset: u32,
+	/// Maximum number of quests returned.
+	pub limit: u32,
+}
+
+impl QuestFilter {
+	pub fn matches(&self, status: &QuestStatus, quest: &AnyQuest) -> bool {
+		let base = quest.get_base();
+
+		self.status.as_ref().map_or(true, |expected| expected == status)
+			&& self.quest_type.as_ref().map_or(true, |expected| *expected == quest.get_quest_type())
+			&& self.provider.map_or(true, |expected| expected == base.provider)
+			&& self.institution_name.as_ref().map_or(true, |expected| *expected == base.institution_name)
+			&& self.skill_token_name.as_ref().map_or(true, |expected| *expected == base.skill_token_name)
+			&& self.deadline_from.map_or(true, |from| base.deadline >= from)
+			&& self.deadline_to.map_or(true, |to| base.deadline <= to)
+	}
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -627,4 +1891,101 @@ pub struct State {
 	pub approved_providers: Vec<ActorId>,
 	pub quests_to_tiers: Vec<(QuestId, QuestType)>,
 	pub minumum_free_gradings: u8,
-}
\ No newline at end of file
+	pub reputation_contract: ActorId,
+	pub consumed_skill_nfts: Vec<(TokenId, QuestId)>,
+	pub skill_token_ids: Vec<(SkillToken, TokenId)>,
+	pub pending_rewards: Vec<(MessageId, PendingReward)>,
+	pub quest_nonce: u64,
+	pub seeker_quests: Vec<(ActorId, Vec<QuestId>)>,
+	pub arbiters: Vec<ActorId>,
+	pub appeals: Vec<((QuestId, ActorId), Appeal)>,
+	pub unclaimed_payouts: Vec<(ActorId, u128)>,
+}
+
+impl State {
+	/// Find the quest of the given id in the mapping of its tier.
+	pub fn get_quest(&self, quest_id: &QuestId) -> Option<AnyQuest> {
+		let (_, quest_type) = self.quests_to_tiers.iter().find(|(id, _)| id == quest_id)?;
+
+		match quest_type {
+			QuestType::BaseTier => self.base_tier_quests.iter().find(|(id, _)| id == quest_id).map(|(_, quest)| AnyQuest::BaseTier(quest.clone())),
+			QuestType::MidTier => self.mid_tier_quests.iter().find(|(id, _)| id == quest_id).map(|(_, quest)| AnyQuest::MidTier(quest.clone())),
+			QuestType::TopTier => self.top_tier_quests.iter().find(|(id, _)| id == quest_id).map(|(_, quest)| AnyQuest::TopTier(quest.clone())),
+			QuestType::Dedicated => self.dedicated_quests.iter().find(|(id, _)| id == quest_id).map(|(_, quest)| AnyQuest::Dedicated(quest.clone())),
+		}
+	}
+
+	/// List the quests matching the query, each with its status.
+	pub fn list_quests(&self, query: &QuestQuery) -> Vec<(QuestId, QuestStatus, AnyQuest)> {
+		let mut quests: Vec<(QuestId, QuestStatus, AnyQuest)> = self.quest_status
+			.iter()
+			.filter_map(|(quest_id, status)| self.get_quest(quest_id).map(|quest| (quest_id.clone(), status.clone(), quest)))
+			.filter(|(_, status, quest)| query.filter.matches(status, quest))
+			.collect();
+
+		// Quest ids are generated from a counter, so they sort numerically in creation order
+		let creation = |quest_id: &QuestId| quest_id.parse::<u64>().unwrap_or(u64::MAX);
+		match query.order {
+			QuestOrder::Creation => quests.sort_by_key(|(quest_id, _, _)| creation(quest_id)),
+			QuestOrder::Deadline => quests.sort_by_key(|(quest_id, _, quest)| (quest.get_base().deadline, creation(quest_id))),
+		}
+
+		quests
+			.into_iter()
+			.skip(query.offset as usize)
+			.take(query.limit as usize)
+			.collect()
+	}
+
+	/// List the quests the seeker has committed to, with the seeker's status and grading in each.
+	pub fn get_seeker_quests(&self, seeker: ActorId) -> Vec<(QuestId, QuestType, SeekerStatus, Option<Gradings>)> {
+		let quest_ids = match self.seeker_quests.iter().find(|(id, _)| *id == seeker) {
+			Some((_, quest_ids)) => quest_ids,
+			None => return Vec::new(),
+		};
+
+		quest_ids
+			.iter()
+			.filter_map(|quest_id| {
+				let quest = self.get_quest(quest_id)?;
+				// Team members share the status of their leader
+				let seeker = match &quest {
+					AnyQuest::TopTier(quest) => quest.team_leader(seeker).unwrap_or(seeker),
+					_ => seeker,
+				};
+				let base = quest.get_base();
+				let status = base.submissions.get(&seeker)?.clone();
+				let gradings = base.gradings.get(&seeker).cloned().flatten();
+				Some((quest_id.clone(), quest.get_quest_type(), status, gradings))
+			})
+			.collect()
+	}
+
+	/// The appeal of the seeker against the rejection in the quest.
+	pub fn get_appeal(&self, quest_id: &QuestId, seeker: ActorId) -> Option<Appeal> {
+		self.appeals
+			.iter()
+			.find(|((id, appellant), _)| id == quest_id && *appellant == seeker)
+			.map(|(_, appeal)| appeal.clone())
+	}
+
+	/// The quest and every quest it depends on, each with its direct prerequisites.
+	pub fn get_prerequisite_graph(&self, quest_id: &QuestId) -> Vec<(QuestId, Vec<Prerequisite>)> {
+		let mut graph: Vec<(QuestId, Vec<Prerequisite>)> = Vec::new();
+		let mut pending = vec![quest_id.clone()];
+
+		while let Some(quest_id) = pending.pop() {
+			if graph.iter().any(|(id, _)| *id == quest_id) {
+				continue;
+			}
+			let prerequisites = match self.get_quest(&quest_id) {
+				Some(quest) => quest.get_base().prerequisites.clone(),
+				None => continue,
+			};
+			pending.extend(prerequisites.iter().map(|prerequisite| prerequisite.quest_id.clone()));
+			graph.push((quest_id, prerequisites));
+		}
+
+		graph
+	}
+}
@@ -1,8 +1,21 @@
 #![no_std]
 
-use gstd::{collections::BTreeMap, exec, msg, prelude::*, ActorId};
+use gstd::{collections::BTreeMap, exec, msg, prelude::*, ActorId, MessageId, ReservationId};
 use quest_io::*;
 use quest_io::QuestId;
+use quest_io::helper_functions::check_skill_nft;
+use reputation_io::{MTKAction, MTKError, MTKEvent, Quest as QuestDetails, SkillNftMetadata, TokenId};
+
+/// Amount of skill FTs issued to a seeker who passed a quest
+const SKILL_TOKEN_REWARD: u128 = 1;
+/// Gas reserved for processing the reply of each reward message
+const REPLY_DEPOSIT: u64 = 10_000_000_000;
+/// Gas reserved for processing each delayed deadline message
+const DEADLINE_GAS: u64 = 10_000_000_000;
+/// Deadlines further away can't be reached by a single delayed message
+const MAX_DEADLINE_DELAY: u64 = u32::MAX as u64 - 1;
+/// Blocks after grading during which a rejected seeker can appeal, about half a day
+const APPEAL_WINDOW: u32 = 14_400;
 
 #[derive(Default, Encode, Decode, Debug, TypeInfo)]
 pub struct Quests {
@@ -23,6 +36,25 @@ pub struct Quests {
         /// For fast search of a quest without the need to loop through the keys of the quest mappings
         pub quests_to_tiers: BTreeMap<QuestId, QuestType>,
         pub minumum_free_gradings: u8,
+        /// The reputation contract used to verify and issue skill tokens and NFTs
+        pub reputation_contract: ActorId,
+        /// Skill NFTs that have been used to claim mid-tier quests: `TokenId` -> `QuestId`
+        pub consumed_skill_nfts: BTreeMap<TokenId, QuestId>,
+        /// Skill FTs in the reputation contract issued for each skill token: `SkillToken` -> `TokenId`
+        pub skill_token_ids: BTreeMap<SkillToken, TokenId>,
+        /// Reward messages sent to the reputation contract waiting for their replies: `MessageId` -> `PendingReward`
+        pub pending_rewards: BTreeMap<MessageId, PendingReward>,
+        /// Monotonic counter used to generate quest ids, the next quest gets the current value
+        pub quest_nonce: u64,
+        /// Quests each seeker has committed to, in commit order: `ActorId` -> `Vec<QuestId>`
+        /// The seeker's status and grading are kept in the quest itself, see `Base::submissions`.
+        pub seeker_quests: BTreeMap<ActorId, Vec<QuestId>>,
+        /// Besides the admin, they can resolve appeals
+        pub arbiters: Vec<ActorId>,
+        /// Appeals against rejections: (`QuestId`, seeker) -> `Appeal`
+        pub appeals: BTreeMap<(QuestId, ActorId), Appeal>,
+        /// Value that could not be sent, kept for the recipient to claim: `ActorId` -> amount
+        pub unclaimed_payouts: BTreeMap<ActorId, u128>,
 }
 
 static mut CONTRACT: Option<Quests> = None;
@@ -35,208 +67,494 @@ extern "C" fn init() {
             admin: msg::source(),
             approved_providers: init_info.approved_providers,
             minumum_free_gradings: init_info.minumum_free_gradings,
+            reputation_contract: init_info.reputation_contract,
+            skill_token_ids: init_info.skill_tokens.into_iter().collect(),
             ..Default::default()
         });
     }
 
-    let _ = msg::reply(QuestEvent::Ok { msg: String::from("Quest Contract Initiated!") }, 0);
+    let _ = msg::reply(QuestEvent::Initialized, 0);
 }
 
-#[no_mangle]
-extern "C" fn handle() {
+#[gstd::async_main(handle_reply = handle_reward_reply)]
+async fn main() {
     let action: QuestAction = msg::load().expect("Failed to load action");
     let quests: &mut Quests = unsafe { CONTRACT.as_mut().expect("Quest contract not initialized.") };
 
     match action {
         QuestAction::Publish { quest_type, quest_info } => {
-            let _ = msg::reply(quests.publish(quest_type, quest_info), 0);
+            reply(quests.publish(quest_type, quest_info));
         },
-        QuestAction::Commit { quest_id } => {
-            let _ = msg::reply(quests.commit(&quest_id), 0);
+        QuestAction::Commit { quest_id, skill_nft, passcode } => {
+            reply(quests.commit(&quest_id, skill_nft, passcode).await);
         },
         QuestAction::Submit { quest_id, submission } => {
-            let _ = msg::reply(quests.submit(&quest_id, submission), 0);
+            reply(quests.submit(&quest_id, submission));
         },
-        QuestAction::Grade { quest_id, commiter, submission, grading} => {
-            let _ = msg::reply(quests.grade(&quest_id, commiter, submission, grading), 0);
+        QuestAction::Grade { quest_id, commiter, submission, grading, feedback } => {
+            reply(quests.grade(&quest_id, commiter, submission, grading, feedback));
         },
         QuestAction::Modify { quest_id, base_info } => {
-            let _ = msg::reply(quests.modify(&quest_id, base_info), 0);
+            reply(quests.modify(&quest_id, base_info));
         },
         QuestAction::Close { quest_id } => {
-            let _ = msg::reply(quests.close(&quest_id), 0);
+            reply(quests.close(&quest_id));
+        },
+        QuestAction::RequestRevision { quest_id, commiter, submission, feedback } => {
+            reply(quests.request_revision(&quest_id, commiter, submission, feedback));
+        },
+        QuestAction::Withdraw { quest_id } => {
+            reply(quests.withdraw(&quest_id));
+        },
+        QuestAction::RetryRewards { quest_id, seeker } => {
+            reply(quests.retry_rewards(&quest_id, seeker));
+        },
+        QuestAction::AssignWinners { quest_id, winners } => {
+            reply(quests.assign_winners(&quest_id, winners));
+        },
+        QuestAction::RefundPrize { quest_id } => {
+            reply(quests.refund_prize(&quest_id));
+        },
+        QuestAction::ClaimPayout => {
+            reply(quests.claim_payout());
+        },
+        QuestAction::AddProvider { provider } => {
+            reply(quests.add_provider(provider));
+        },
+        QuestAction::RemoveProvider { provider, freeze_quests } => {
+            reply(quests.remove_provider(provider, freeze_quests));
+        },
+        QuestAction::ListProviders => {
+            reply(quests.list_providers());
         },
+        QuestAction::Appeal { quest_id, reason } => {
+            reply(quests.appeal(&quest_id, reason));
+        },
+        QuestAction::ResolveAppeal { quest_id, seeker, decision } => {
+            reply(quests.resolve_appeal(&quest_id, seeker, decision));
+        },
+        QuestAction::SetQuestRoles { quest_id, graders, managers } => {
+            reply(quests.set_quest_roles(&quest_id, graders, managers));
+        },
+        QuestAction::CreateTeam { quest_id } => {
+            reply(quests.create_team(&quest_id));
+        },
+        QuestAction::InviteToTeam { quest_id, member } => {
+            reply(quests.invite_to_team(&quest_id, member));
+        },
+        QuestAction::AcceptInvite { quest_id, leader } => {
+            reply(quests.accept_invite(&quest_id, leader));
+        },
+        QuestAction::SetArbiters { arbiters } => {
+            reply(quests.set_arbiters(arbiters));
+        },
+        QuestAction::SealSubmission { quest_id, commitment } => {
+            reply(quests.seal_submission(&quest_id, commitment));
+        },
+        QuestAction::RevealSubmission { quest_id, submission, salt } => {
+            reply(quests.reveal_submission(&quest_id, submission, salt));
+        },
+        QuestAction::ExpireDeadline { quest_id, deadline } => {
+            // Only the quest contract itself can expire deadlines, anyone else gets an error and the value back.
+            if msg::source() != exec::program_id() {
+                reply(Err(QuestError::Unauthorized));
+            } else {
+                // Sent by the contract itself, so there is no one to reply to.
+                quests.expire_deadline(&quest_id, deadline);
+            }
+        },
+    }
+}
+
+/// Reply to the sender, the value attached to a failed action is sent back.
+/// Events of successful actions are broadcast as well, except for queries.
+fn reply(result: Result<QuestEvent, QuestError>) {
+    if let Ok(event) = &result {
+        if !matches!(event, QuestEvent::Providers { .. }) {
+            broadcast(event);
+        }
+    }
+    let value = if result.is_err() { msg::value() } else { 0 };
+    let _ = msg::reply(result, value);
+}
+
+/// Broadcast the event to `ActorId::zero()`, where off-chain indexers pick it up.
+fn broadcast(event: &QuestEvent) {
+    let _ = msg::send(ActorId::zero(), event, 0);
+}
+
+/// Replies from the reputation contract confirm or fail the pending rewards.
+fn handle_reward_reply() {
+    let quests: &mut Quests = unsafe { CONTRACT.as_mut().expect("Quest contract not initialized.") };
+    let reply_to = msg::reply_to().expect("Failed to query reply_to data");
+
+    // Replies to other messages, e.g. skill NFT verifications, are handled by their futures.
+    if !quests.pending_rewards.contains_key(&reply_to) {
+        return;
     }
+
+    let succeeded = msg::reply_code().map(|code| code.is_success()).unwrap_or(false)
+        && matches!(msg::load::<Result<MTKEvent, MTKError>>(), Ok(Ok(_)));
+    quests.confirm_reward(reply_to, succeeded);
 }
 
 #[no_mangle]
 extern "C" fn state() {
-    let contract = unsafe { CONTRACT.take().expect("Unexpected error in taking state") };
+    // Borrow the contract, so the program keeps working after its state is read
+    let contract = unsafe { CONTRACT.as_ref().expect("Quest contract not initialized.") };
     msg::reply::<State>(contract.into(), 0).expect(
         "Failed to encode or reply with `<ContractMetadata as Metadata>::State` from `state()`",
     );
 }
 
 impl Quests {
-    fn publish(&mut self, quest_type: QuestType, quest_info: IncomingQuest) -> QuestEvent {
+    fn publish(&mut self, quest_type: QuestType, quest_info: IncomingQuest) -> Result<QuestEvent, QuestError> {
         // Only approved providers can publish quests
         if !self.is_approved(msg::source()) {
-            return QuestEvent::Err { msg: String::from("You are not an approved provider!") }
+            return Err(QuestError::NotApprovedProvider)
         }
         
-        let quest_id = quest_id_gen();
+        // The deadline must be in the future, within reach of the deadline message
+        if quest_info.deadline <= exec::block_height() as u64 || quest_info.deadline - exec::block_height() as u64 > MAX_DEADLINE_DELAY {
+            return Err(QuestError::InvalidDeadline);
+        }
+
+        // Only top-tier quests carry a prize
+        if quest_type != QuestType::TopTier && msg::value() > 0 {
+            return Err(QuestError::PrizeNotAccepted);
+        }
+
+        // Only top-tier quests allow teams
+        if quest_type != QuestType::TopTier && quest_info.max_team_size > 1 {
+            return Err(QuestError::TeamsNotSupported);
+        }
+        // The rewards of the whole team are sent when the leader is graded
+        if quest_info.max_team_size > MAX_TEAM_SIZE {
+            return Err(QuestError::TeamTooLarge);
+        }
+
+        // Only dedicated quests are protected by a passcode
+        if quest_type != QuestType::Dedicated && quest_info.passcode_hash.is_some() {
+            return Err(QuestError::PasscodeNotSupported);
+        }
+
+        // Only top-tier quests take sealed submissions
+        if quest_type != QuestType::TopTier && quest_info.reveal_window > 0 {
+            return Err(QuestError::SealingNotSupported);
+        }
+        // Sealed submissions can't be revised, they are only revealed after the deadline
+        if quest_info.reveal_window > 0 && quest_info.max_revisions > 0 {
+            return Err(QuestError::RevisionsNotSupported);
+        }
+
+        // Prerequisites must refer to published quests
+        if quest_info.prerequisites.iter().any(|prerequisite| !self.quests_to_tiers.contains_key(&prerequisite.quest_id)) {
+            return Err(QuestError::InvalidPrerequisite);
+        }
+
+        // The rubric must be able to map scores onto gradings
+        if quest_info.rubric.as_ref().is_some_and(|rubric| !rubric.is_valid()) {
+            return Err(QuestError::InvalidRubric);
+        }
+
+        // Milestones follow each other between now and the quest deadline
+        if quest_info.milestones.len() > MAX_MILESTONES {
+            return Err(QuestError::InvalidMilestones);
+        }
+        let mut milestone_deadline = exec::block_height() as u64;
+        for milestone in quest_info.milestones.iter() {
+            if milestone.deadline <= milestone_deadline || milestone.deadline > quest_info.deadline {
+                return Err(QuestError::InvalidMilestones);
+            }
+            milestone_deadline = milestone.deadline;
+        }
+
+        let quest_id = self.quest_id_gen();
 
         match quest_type {
             QuestType::BaseTier => {
                 // 0. Sanity check first
                 // Free gradings need to above a threshold
                 if quest_info.free_gradings < self.minumum_free_gradings {
-                    return QuestEvent::Err { msg: String::from("Free gradings need to be above the minimum threshold!") }
+                    return Err(QuestError::NotEnoughFreeGradings)
                 }
-                // 1. Construct the actual quest based on the incoming quest info
+                // 1. Schedule the deadlines, nothing is published if they can't be scheduled
+                self.schedule_deadline(&quest_id, Deadline::Final, quest_info.deadline)?;
+                // 2. Construct the actual quest based on the incoming quest info
                 let base_tier_quest = BaseTierQuest {
                     base: self.construct_quest_base(quest_info.clone()),
                     // TODO: need to check against the minimum allowance
                     free_gradings: quest_info.free_gradings,
                 };
-                // 2. Insert the incoming quests into the quest mapping
+                // 3. Insert the incoming quests into the quest mapping
                 self.base_tier_quests.insert(quest_id.clone(), base_tier_quest);
-                // 3. Insert the quest status into the quest status mapping
+                // 4. Insert the quest status into the quest status mapping
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
-                // 4. Insert the quest id into the quest to tier mapping
+                // 5. Insert the quest id into the quest to tier mapping
                 self.quests_to_tiers.insert(quest_id.clone(), QuestType::BaseTier);
-                // 5. Return the event
-                QuestEvent::Ok { msg: String::from(quest_id) }
+                // 6. Return the event
+                Ok(QuestEvent::Published { quest_id, quest_type, provider: msg::source() })
             },
             QuestType::MidTier => {
                 // 0. Sanity check first
                 // Free gradings need to above a threshold
                 if quest_info.free_gradings < self.minumum_free_gradings {
-                    return QuestEvent::Err { msg: String::from("Free gradings need to be above the minimum threshold!") }
+                    return Err(QuestError::NotEnoughFreeGradings)
                 }
-                // 1. Construct the actual quest based on the incoming quest info
+                // 1. Schedule the deadlines, nothing is published if they can't be scheduled
+                self.schedule_deadline(&quest_id, Deadline::Final, quest_info.deadline)?;
+                // 2. Construct the actual quest based on the incoming quest info
                 let mid_tier_quest = MidTierQuest {
                     base: self.construct_quest_base(quest_info.clone()),
                     // TODO: need to check against the minimum allowance
                     free_gradings: quest_info.free_gradings,
+                    stake: quest_info.stake,
                     hiring_for: quest_info.hiring_for,
                     skill_tags: quest_info.skill_tags,
                     reputation_nft: quest_info.reputation_nft,
                 };
-                // 2. Insert the incoming quests into the quest mapping
+                // 3. Insert the incoming quests into the quest mapping
                 self.mid_tier_quests.insert(quest_id.clone(), mid_tier_quest);
-                // 3. Insert the quest status into the quest status mapping
+                // 4. Insert the quest status into the quest status mapping
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
-                // 4. Insert the quest id into the quest to tier mapping
+                // 5. Insert the quest id into the quest to tier mapping
                 self.quests_to_tiers.insert(quest_id.clone(), QuestType::MidTier);
-                // 5. Return the event
-                QuestEvent::Ok { msg: String::from(quest_id) }
+                // 6. Return the event
+                Ok(QuestEvent::Published { quest_id, quest_type, provider: msg::source() })
             },
             QuestType::TopTier => {
                 // 0. Sanity check first
-                // Application deadline must in the future
-                if quest_info.application_deadline < exec::block_height() {
-                    return QuestEvent::Err { msg: String::from("Application deadline needs to be in the future!") };
+                // Application deadline must in the future, and no later than the deadline
+                if quest_info.application_deadline < exec::block_height() || quest_info.application_deadline as u64 > quest_info.deadline {
+                    return Err(QuestError::InvalidDeadline);
+                }
+                // The prize must be attached, and fully split among the ranks
+                let prize = msg::value();
+                if prize == 0 {
+                    return Err(QuestError::PrizeRequired);
                 }
-                // 1. Construct the actual quest based on the incoming quest info
+                if quest_info.prize_split.iter().map(|percentage| *percentage as u32).sum::<u32>() != 100 {
+                    return Err(QuestError::InvalidPrizeSplit);
+                }
+                // 1. Schedule the deadlines, nothing is published if they can't be scheduled
+                self.schedule_deadline(&quest_id, Deadline::Application, quest_info.application_deadline as u64)?;
+                self.schedule_deadline(&quest_id, Deadline::Final, quest_info.deadline)?;
+                // 2. Construct the actual quest based on the incoming quest info
                 let top_tier_quest = TopTierQuest {
                     base: self.construct_quest_base(quest_info.clone()),
-                    prize: quest_info.prize,
+                    prize,
+                    prize_split: quest_info.prize_split.clone(),
+                    winners: Vec::new(),
+                    prize_balance: prize,
+                    prize_finalized: false,
                     application_deadline: quest_info.application_deadline,
                     reputation_nft: quest_info.reputation_nft,
+                    max_team_size: quest_info.max_team_size,
+                    teams: BTreeMap::new(),
+                    reveal_window: quest_info.reveal_window,
                 };
-                // 2. Insert the incoming quests into the quest mapping
+                // 3. Insert the incoming quests into the quest mapping
                 self.top_tier_quests.insert(quest_id.clone(), top_tier_quest);
-                // 3. Insert the quest status into the quest status mapping
+                // 4. Insert the quest status into the quest status mapping
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
-                // 4. Insert the quest id into the quest to tier mapping
+                // 5. Insert the quest id into the quest to tier mapping
                 self.quests_to_tiers.insert(quest_id.clone(), QuestType::TopTier);
-                // 5. Return the event
-                QuestEvent::Ok { msg: String::from(quest_id) }
+                // 6. Return the event
+                Ok(QuestEvent::Published { quest_id, quest_type, provider: msg::source() })
             },
             QuestType::Dedicated => {
-                // 1. Construct the actual quest based on the incoming quest info
+                // 1. Schedule the deadlines, nothing is published if they can't be scheduled
+                self.schedule_deadline(&quest_id, Deadline::Final, quest_info.deadline)?;
+                // 2. Construct the actual quest based on the incoming quest info
                 let dedicated_quest = DedicatedQuest {
                     base: self.construct_quest_base(quest_info.clone()),
                     dedicated_to: quest_info.dedicated_to,
+                    passcode_hash: quest_info.passcode_hash,
+                    passcode_salt: quest_info.passcode_salt,
                 };
-                // 2. Insert the incoming quests into the quest mapping
+                // 3. Insert the incoming quests into the quest mapping
                 self.dedicated_quests.insert(quest_id.clone(), dedicated_quest);
-                // 3. Insert the quest status into the quest status mapping
+                // 4. Insert the quest status into the quest status mapping
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
-                // 4. Insert the quest id into the quest to tier mapping
+                // 5. Insert the quest id into the quest to tier mapping
                 self.quests_to_tiers.insert(quest_id.clone(), QuestType::Dedicated);
-                // 5. Return the event
-                QuestEvent::Ok { msg: String::from(quest_id) }
+                // 6. Return the event
+                Ok(QuestEvent::Published { quest_id, quest_type, provider: msg::source() })
             }
         }
     }
 
     /// Opportunity seekers commit (claim) a quest
-    fn commit(&mut self, quest_id: &QuestId) -> QuestEvent {
+    async fn commit(&mut self, quest_id: &QuestId, skill_nft: Option<TokenId>, passcode: Option<String>) -> Result<QuestEvent, QuestError> {
         // Everyone can commit to a quest, but that quest must exists
         if !self.quest_status.contains_key(quest_id) {
-            return QuestEvent::Err { msg: String::from("Quest does not exist!") };
+            return Err(QuestError::QuestNotFound);
         }
 
         // 1. Check if the quest is open
-        // Notice: quest is automatically closed after the deadline is passed, so we don't need to check that here
-        if self.quest_status.get(quest_id).unwrap() != &QuestStatus::Open {
-            return QuestEvent::Err { msg: String::from("Quest is not open!") };
+        // Notice: quest is automatically closed or finished after the deadlines are passed, so we don't need to check that here
+        self.check_open(quest_id)?;
+
+        let seeker = msg::source();
+        let is_mid_tier = self.quests_to_tiers.get(quest_id).unwrap() == &QuestType::MidTier;
+
+        // Only mid-tier quests can be staked for
+        if !is_mid_tier && msg::value() > 0 {
+            return Err(QuestError::StakeNotAccepted);
         }
-        
+
+        // The seeker must have passed the prerequisites with their minimum gradings
+        self.check_prerequisites(quest_id, seeker)?;
+
+        // Dedicated quests with a passcode can only be claimed by seekers who know it
+        if self.dedicated_quests.get(quest_id).is_some_and(|quest| !quest.check_passcode(passcode.as_deref())) {
+            return Err(QuestError::IncorrectPasscode);
+        }
+
+        // 2. Mid-tier quests require the seeker to hold a skill NFT in the reputation contract
+        if is_mid_tier {
+            let token_id = match skill_nft {
+                Some(token_id) => token_id,
+                None => return Err(QuestError::SkillNftRequired),
+            };
+            if self.consumed_skill_nfts.contains_key(&token_id) {
+                return Err(QuestError::SkillNftConsumed);
+            }
+            // The skill NFT must be of the skill the quest requires
+            let skill = self.mid_tier_quests.get(quest_id).unwrap().skill_tags;
+            if !check_skill_nft(self.reputation_contract, seeker, token_id, skill).await {
+                return Err(QuestError::SkillNftNotFound);
+            }
+            // Other messages may have been processed while waiting for the reputation contract,
+            // so the quest and the skill NFT need to be checked again.
+            self.check_open(quest_id)?;
+            if self.consumed_skill_nfts.contains_key(&token_id) {
+                return Err(QuestError::SkillNftConsumed);
+            }
+        }
+
         let quest = self.get_quest(quest_id);
 
         // Notice that only mid-tier and top-tier capacity will change over each commit.
         // The initial value of other types of quest's capacity must set to > 1.
-        if let Err(e) = quest.commit(msg::source()) {
-            return QuestEvent::Err { msg: e };
+        if let Err(e) = quest.commit(seeker) {
+            return Err(e);
         } else {
+            let members = quest.get_team_members(seeker);
             if quest.get_capacity() == 0 {
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Full);
             }
-            return QuestEvent::Ok { msg: String::from("Quest committed!") };
+            // The skill NFT can't be used to claim another mid-tier quest anymore
+            if is_mid_tier {
+                self.consumed_skill_nfts.insert(skill_nft.unwrap(), quest_id.clone());
+            }
+            // Team members follow the quest through their leader
+            for committer in core::iter::once(seeker).chain(members) {
+                self.seeker_quests.entry(committer).or_default().push(quest_id.clone());
+            }
+            return Ok(QuestEvent::Committed { quest_id: quest_id.clone(), seeker });
         }
     }
 
     /// The committer who committed to the quest can submit to the quest only once.
     /// There are not much to check for the submission action, since the check is done during the commit process.
-    fn submit(&mut self, quest_id: &QuestId, submission: Submmision) -> QuestEvent {
+    fn submit(&mut self, quest_id: &QuestId, submission: SubmissionRef) -> Result<QuestEvent, QuestError> {
         // The quest must exists
         if !self.quest_status.contains_key(quest_id) {
-            return QuestEvent::Err { msg: String::from("Quest does not exist!") };
+            return Err(QuestError::QuestNotFound);
         }
 
         if self.quest_status.get(quest_id).unwrap() == &QuestStatus::Finished {
-            return QuestEvent::Err { msg: String::from("You can't submit after a quest is finished!") };
+            return Err(QuestError::QuestFinished);
+        }
+
+        // Graders and the reputation NFT rely on the submission pointing to one exact artifact
+        if !submission.is_valid() {
+            return Err(QuestError::InvalidSubmission);
         }
 
         // Find where the quest is in the quest mappings
         let quest = self.get_quest(quest_id);
 
-        if let Err(e) = quest.submit(msg::source(), submission) {
-            return QuestEvent::Err { msg: e };
+        let seeker = msg::source();
+        // The deadline message may still be on its way, so the deadline is checked here as well.
+        // Seekers of quests with milestones submit by the deadline of their current milestone.
+        if exec::block_height() as u64 >= quest.get_base().current_deadline(seeker) {
+            return Err(QuestError::DeadlinePassed);
+        }
+
+        if let Err(e) = quest.submit(seeker, submission) {
+            return Err(e);
         } else {
-            return QuestEvent::Ok { msg: String::from("Submission successful!") };
+            // The seeker did not abandon the quest, so the stake is refunded.
+            if let Some(stake) = quest.get_base_mut().stakes.remove(&seeker) {
+                self.notify(seeker, QuestEvent::StakeRefunded { quest_id: quest_id.clone(), seeker, amount: stake }, stake);
+            }
+            return Ok(QuestEvent::Submitted { quest_id: quest_id.clone(), seeker });
         }
     }
 
-    fn grade(&mut self, quest_id: &QuestId, commiter: ActorId, submission: Submmision, gradings: Gradings) -> QuestEvent {
+    fn grade(&mut self, quest_id: &QuestId, commiter: ActorId, submission: SubmissionRef, evaluation: Evaluation, feedback: String) -> Result<QuestEvent, QuestError> {
         // The quest must exists
         if !self.quest_status.contains_key(quest_id) {
-            return QuestEvent::Err { msg: String::from("Quest does not exist!") };
+            return Err(QuestError::QuestNotFound);
         }
         // Find where the quest_id is in the quest mappings
         let quest = self.get_quest(quest_id);
 
-        if let Err(e) = quest.grade(msg::source(), commiter, submission, gradings) {
-            return QuestEvent::Err { msg: e };
+        // Scores are mapped onto gradings by the rubric of the quest
+        let (gradings, assessment) = match (evaluation, &quest.get_base().rubric) {
+            (Evaluation::Gradings(gradings), None) => (gradings, Assessment { feedback, graded_at: exec::block_height(), grader: msg::source(), ..Default::default() }),
+            (Evaluation::Scores(scores), Some(rubric)) => {
+                let overall_score = rubric.overall_score(&scores)?;
+                (rubric.gradings(overall_score), Assessment { scores, overall_score: Some(overall_score), feedback, graded_at: exec::block_height(), grader: msg::source() })
+            },
+            (Evaluation::Gradings(_), Some(_)) => return Err(QuestError::ScoresRequired),
+            (Evaluation::Scores(_), None) => return Err(QuestError::ScoresNotAccepted),
+        };
+
+        let milestone = quest.get_base().current_milestone(commiter);
+        if let Err(e) = quest.grade(msg::source(), commiter, submission.clone(), gradings.clone()) {
+            return Err(e);
         } else {
-            // If the capacity is 1, then that means the previous status if Full, so we change it to Open.
-            if quest.get_capacity() == 1 {
+            quest.get_base_mut().assessments.insert(commiter, assessment);
+            // A passed milestone before the last one moves the seeker on to the next milestone,
+            // the rewards are issued once the whole quest is passed.
+            if quest.get_base().submissions.get(&commiter) == Some(&SeekerStatus::Waiting) {
+                // Publishing caps the milestones at `MAX_MILESTONES`, so the index fits
+                return Ok(QuestEvent::MilestonePassed { quest_id: quest_id.clone(), seeker: commiter, milestone: milestone as u8, grading: gradings });
+            }
+            // The released slot opens the quest again, closed and finished quests stay that way.
+            let capacity = quest.get_capacity();
+            if self.quest_status.get(quest_id) == Some(&QuestStatus::Full) && capacity > 0 {
                 self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
             }
-            return QuestEvent::Ok { msg: String::from("Quest successfully graded!") };
+            // Seekers who passed the quest get their rewards from the reputation contract.
+            if gradings != Gradings::Reject {
+                self.issue_rewards(quest_id, commiter, submission);
+            }
+            return Ok(QuestEvent::Graded { quest_id: quest_id.clone(), seeker: commiter, grading: gradings });
+        }
+    }
+
+    /// Providers can ask for a revision instead of a final grade, the seeker then submits again before the deadline.
+    fn request_revision(&mut self, quest_id: &QuestId, commiter: ActorId, submission: SubmissionRef, feedback: String) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        // No more submissions are accepted once the quest is finished
+        if self.quest_status.get(quest_id).unwrap() == &QuestStatus::Finished {
+            return Err(QuestError::QuestFinished);
+        }
+
+        let quest = self.get_quest(quest_id);
+        match quest.request_revision(msg::source(), commiter, submission, feedback.clone()) {
+            Ok(remaining_rounds) => {
+                return Ok(QuestEvent::RevisionRequested { quest_id: quest_id.clone(), seeker: commiter, feedback, remaining_rounds });
+            },
+            Err(e) => return Err(e),
         }
     }
 
@@ -244,43 +562,375 @@ impl Quests {
     /// since there are not efficient ways to know which part got modified and which part did not.
     /// 
     /// Currently, only base inforamtion are modifiable.
-    fn modify(&mut self, quest_id: &QuestId, base_info: Modifiable) -> QuestEvent {
-        let quest = self.get_quest(quest_id);
-        
+    fn modify(&mut self, quest_id: &QuestId, base_info: Modifiable) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        // The modified deadline must be in the future and within reach as well
+        if base_info.deadline <= exec::block_height() as u64 || base_info.deadline - exec::block_height() as u64 > MAX_DEADLINE_DELAY {
+            return Err(QuestError::InvalidDeadline);
+        }
+        // Top-tier quests stop taking commits before the deadline
+        if self.top_tier_quests.get(quest_id).is_some_and(|quest| quest.application_deadline as u64 > base_info.deadline) {
+            return Err(QuestError::InvalidDeadline);
+        }
 
+        let quest = self.get_quest_ref(quest_id);
+        // Milestones must still end by the quest deadline
+        if quest.get_base().milestones.last().is_some_and(|milestone| milestone.deadline > base_info.deadline) {
+            return Err(QuestError::InvalidMilestones);
+        }
+        let deadline = base_info.deadline;
+        // The previously scheduled deadline message will be ignored once it arrives.
+        // The new one is scheduled first, so the quest is left as it is if the deadline can't be scheduled.
+        if deadline != quest.get_deadline() {
+            self.schedule_deadline(quest_id, Deadline::Final, deadline)?;
+        }
+
+        let quest = self.get_quest(quest_id);
         if let Err(e) = quest.modify(msg::source(), base_info) {
-            return QuestEvent::Err { msg: e };
+            return Err(e);
         } else {
-            return QuestEvent::Ok { msg: String::from("Quest modified!") };
+            return Ok(QuestEvent::Modified { quest_id: quest_id.clone() });
+        }
+    }
+
+    /// Seekers leave the quest before submitting, the refunds are decided by each tier.
+    fn withdraw(&mut self, quest_id: &QuestId) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        let status = match self.quest_status.get(quest_id) {
+            Some(status) => status.clone(),
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        // Nothing is left to withdraw from once the quest is finished
+        if status == QuestStatus::Finished {
+            return Err(QuestError::QuestFinished);
+        }
+
+        let seeker = msg::source();
+        let quest = self.get_quest(quest_id);
+
+        if let Err(e) = quest.withdraw(seeker) {
+            return Err(e);
+        } else {
+            // Withdrawing is abandoning the quest, so the stake stays escrowed and is forfeited at the deadline.
+            // The released slot opens the quest again, closed quests stay closed.
+            if status == QuestStatus::Full && quest.get_capacity() > 0 {
+                self.quest_status.insert(quest_id.clone(), QuestStatus::Open);
+            }
+            return Ok(QuestEvent::Withdrawn { quest_id: quest_id.clone(), seeker });
+        }
+    }
+
+    /// Pay out the prize of a top-tier quest to the ranked winners.
+    fn assign_winners(&mut self, quest_id: &QuestId, winners: Vec<ActorId>) -> Result<QuestEvent, QuestError> {
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        match quest.assign_winners(msg::source(), winners) {
+            Ok(payouts) => {
+                for (winner, amount) in payouts.iter().copied() {
+                    if amount > 0 {
+                        self.notify(winner, QuestEvent::PrizePaid { quest_id: quest_id.clone(), winner, amount }, amount);
+                    }
+                }
+                return Ok(QuestEvent::WinnersAssigned { quest_id: quest_id.clone(), payouts });
+            },
+            Err(e) => return Err(e),
+        }
+    }
+
+    /// Refund the prize that is not paid out to the provider after the quest is finished.
+    fn refund_prize(&mut self, quest_id: &QuestId) -> Result<QuestEvent, QuestError> {
+        if self.quest_status.get(quest_id) != Some(&QuestStatus::Finished) {
+            return Err(QuestError::QuestNotFinished);
+        }
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        match quest.refund_prize(msg::source(), exec::block_height() as u64) {
+            Ok(refund) => {
+                let provider = quest.base.provider;
+                self.transfer(provider, QuestEvent::PrizeRefunded { quest_id: quest_id.clone(), provider, amount: refund }, refund);
+                return Ok(QuestEvent::PrizeRefunded { quest_id: quest_id.clone(), provider, amount: refund });
+            },
+            Err(e) => return Err(e),
+        }
+    }
+
+    /// Send the value kept for the msg sender after a failed payout.
+    fn claim_payout(&mut self) -> Result<QuestEvent, QuestError> {
+        let recipient = msg::source();
+        let amount = match self.unclaimed_payouts.remove(&recipient) {
+            Some(amount) => amount,
+            None => return Err(QuestError::NothingToClaim),
+        };
+
+        if msg::send(recipient, QuestEvent::PayoutClaimed { recipient, amount }, amount).is_err() {
+            self.unclaimed_payouts.insert(recipient, amount);
+            return Err(QuestError::PayoutFailed);
+        }
+        return Ok(QuestEvent::PayoutClaimed { recipient, amount });
+    }
+
+    /// Send the event along with the value to `to`, and broadcast it.
+    fn notify(&mut self, to: ActorId, event: QuestEvent, value: u128) {
+        broadcast(&event);
+        self.transfer(to, event, value);
+    }
+
+    /// Send the event along with the value to `to`.
+    /// The prize and stake bookkeeping is already updated, so value that fails to send is kept for `to` to claim.
+    fn transfer(&mut self, to: ActorId, event: QuestEvent, value: u128) {
+        if msg::send(to, event, value).is_err() && value > 0 {
+            *self.unclaimed_payouts.entry(to).or_default() += value;
+            broadcast(&QuestEvent::PayoutFailed { recipient: to, amount: value });
         }
     }
 
     /// Close is designed to let providers close the quest before the deadline.
     /// After closing, a quest will be marked as Closed, and no more commits are allowed,
     /// but submissions and gradings are still allowed.
-    fn close(&mut self, quest_id: &QuestId) -> QuestEvent {
+    fn close(&mut self, quest_id: &QuestId) -> Result<QuestEvent, QuestError> {
         // The quest must exists
         if !self.quest_status.contains_key(quest_id) {
-            return QuestEvent::Err { msg: String::from("Quest does not exist!") };
+            return Err(QuestError::QuestNotFound);
         }
 
         // Get the quest.
         let quest = self.get_quest(quest_id);
 
-        // Only the owner of the quest can close.
-        if msg::source() != quest.get_owner() {
-            return QuestEvent::Err { msg: String::from("You are not the admin!") };
+        // Only the provider and co-managers of the quest can close.
+        if !quest.get_base().is_manager(msg::source()) {
+            return Err(QuestError::NotManager);
         }
 
         // Only can close if the quest is not in the status of Closed.
         if self.quest_status.get(quest_id).unwrap() == &QuestStatus::Closed {
-            return QuestEvent::Err { msg: String::from("Quest is already closed!") };
+            return Err(QuestError::QuestClosed);
+        }
+
+        // A finished quest is closed already.
+        if self.quest_status.get(quest_id).unwrap() == &QuestStatus::Finished {
+            return Err(QuestError::QuestFinished);
         }
 
         // Close the quest.
         self.quest_status.insert(quest_id.clone(), QuestStatus::Closed);
 
-        return QuestEvent::Ok { msg: String::from("Quest closed!") };
+        return Ok(QuestEvent::Closed { quest_id: quest_id.clone() });
+    }
+
+    /// Schedule a delayed message to the quest contract itself that arrives at the given block height.
+    /// The message is paid from a gas reservation, so it does not depend on the gas left by the publisher.
+    /// Deadlines the gas can't be reserved for are invalid.
+    fn schedule_deadline(&self, quest_id: &QuestId, deadline: Deadline, block_height: u64) -> Result<(), QuestError> {
+        let delay = block_height.saturating_sub(exec::block_height() as u64);
+        // Deadlines are checked against `MAX_DEADLINE_DELAY` when they are set
+        let delay = u32::try_from(delay).map_err(|_| QuestError::InvalidDeadline)?;
+
+        // The reservation is held until the deadline, which can cost more gas than the message has
+        let reservation_id = match ReservationId::reserve(DEADLINE_GAS, delay + 1) {
+            Ok(reservation_id) => reservation_id,
+            Err(_) => return Err(QuestError::InvalidDeadline),
+        };
+        if msg::send_delayed_from_reservation(
+            reservation_id,
+            exec::program_id(),
+            QuestAction::ExpireDeadline { quest_id: quest_id.clone(), deadline },
+            0,
+            delay,
+        )
+        .is_err()
+        {
+            return Err(QuestError::InvalidDeadline);
+        }
+        return Ok(());
+    }
+
+    /// Move the quest to `Closed` at its application deadline and to `Finished` at its final deadline.
+    /// Messages of deadlines that got modified are ignored.
+    fn expire_deadline(&mut self, quest_id: &QuestId, deadline: Deadline) {
+        // The quest must exists
+        let status = match self.quest_status.get(quest_id) {
+            Some(status) => status.clone(),
+            None => return,
+        };
+        let block_height = exec::block_height() as u64;
+
+        match deadline {
+            Deadline::Application => {
+                let application_deadline = match self.top_tier_quests.get(quest_id) {
+                    Some(quest) => quest.application_deadline as u64,
+                    None => return,
+                };
+                if block_height >= application_deadline && (status == QuestStatus::Open || status == QuestStatus::Full) {
+                    self.quest_status.insert(quest_id.clone(), QuestStatus::Closed);
+                    broadcast(&QuestEvent::Closed { quest_id: quest_id.clone() });
+                }
+            },
+            Deadline::Final => {
+                if block_height >= self.get_quest_ref(quest_id).get_deadline() && status != QuestStatus::Finished {
+                    self.quest_status.insert(quest_id.clone(), QuestStatus::Finished);
+                    broadcast(&QuestEvent::Finished { quest_id: quest_id.clone() });
+                    self.forfeit_stakes(quest_id);
+                }
+            },
+        }
+    }
+
+    /// Stakes left after the deadline belong to seekers who abandoned the quest, they go to the provider.
+    fn forfeit_stakes(&mut self, quest_id: &QuestId) {
+        let base = self.get_quest(quest_id).get_base_mut();
+        let forfeited: u128 = core::mem::take(&mut base.stakes).into_values().sum();
+
+        if forfeited > 0 {
+            let provider = base.provider;
+            self.notify(provider, QuestEvent::StakesForfeited { quest_id: quest_id.clone(), provider, amount: forfeited }, forfeited);
+        }
+    }
+
+    /// Issue the skill token of the quest to the seeker,
+    /// mid-tier and top-tier quests also issue their reputation NFT with the graded submission attached.
+    ///
+    /// The seeker stays in `GradedPendingReward` until the reputation contract confirms all rewards.
+    fn issue_rewards(&mut self, quest_id: &QuestId, seeker: ActorId, submission: SubmissionRef) {
+        let quest = self.get_quest_ref(quest_id);
+        let members = quest.get_team_members(seeker);
+        let mut rewards = Vec::new();
+
+        if let Some(id) = self.skill_token_ids.get(&quest.get_base().skill_token_name) {
+            rewards.push(Reward::SkillToken(*id));
+        }
+        if quest.get_reputation_nft().name().is_some() {
+            rewards.push(Reward::ReputationNft(submission));
+        }
+
+        if rewards.is_empty() {
+            return;
+        }
+
+        let base = self.get_quest(quest_id).get_base_mut();
+        if let Some(SeekerStatus::Graded(gradings)) = base.submissions.get(&seeker).cloned() {
+            base.submissions.insert(seeker, SeekerStatus::GradedPendingReward(gradings));
+        }
+
+        // Every member of the seeker's team gets the same rewards
+        for recipient in core::iter::once(seeker).chain(members) {
+            for reward in rewards.iter() {
+                self.send_reward(quest_id, recipient, reward.clone());
+            }
+        }
+    }
+
+    /// Send a reward message to the reputation contract and track it until its reply arrives.
+    fn send_reward(&mut self, quest_id: &QuestId, seeker: ActorId, reward: Reward) {
+        let action = self.reward_action(quest_id, seeker, &reward);
+        let message_id = msg::send(self.reputation_contract, action, 0).expect("Failed to send reward");
+        exec::reply_deposit(message_id, REPLY_DEPOSIT).expect("Failed to deposit gas for the reward reply");
+
+        self.pending_rewards.insert(message_id, PendingReward {
+            quest_id: quest_id.clone(),
+            seeker,
+            reward,
+            failed: false,
+        });
+    }
+
+    /// Build the reputation contract action that issues the reward.
+    fn reward_action(&self, quest_id: &QuestId, seeker: ActorId, reward: &Reward) -> MTKAction {
+        let quest = self.get_quest_ref(quest_id);
+        let base = quest.get_base();
+
+        match reward {
+            Reward::SkillToken(id) => MTKAction::MintFtTo { id: *id, amount: SKILL_TOKEN_REWARD, to: seeker },
+            Reward::ReputationNft(submission) => {
+                let metadata = SkillNftMetadata {
+                    owner: Some(seeker),
+                    title: quest.get_reputation_nft().name(),
+                    description: Some(base.description.clone()),
+                    quest_details: Some(QuestDetails {
+                        id: Some(quest_id.clone()),
+                        publisher: Some(base.provider),
+                        title: Some(base.quest_name.clone()),
+                        description: Some(base.description.clone()),
+                        reward: self.skill_token_ids.get(&base.skill_token_name).copied(),
+                        submission: Some(submission.clone()),
+                    }),
+                    freshness: Some(exec::block_height()),
+                };
+                MTKAction::MintNftTo { to: seeker, metadata }
+            },
+        }
+    }
+
+    /// Settle a pending reward with the outcome reported by the reputation contract.
+    /// Once all rewards of a seeker are confirmed, the seeker is marked as `Graded`.
+    fn confirm_reward(&mut self, message_id: MessageId, succeeded: bool) {
+        if !succeeded {
+            if let Some(pending) = self.pending_rewards.get_mut(&message_id) {
+                pending.failed = true;
+            }
+            return;
+        }
+
+        let PendingReward { quest_id, seeker, .. } = match self.pending_rewards.remove(&message_id) {
+            Some(pending) => pending,
+            None => return,
+        };
+        if self.pending_rewards.values().any(|pending| pending.quest_id == quest_id && pending.seeker == seeker) {
+            return;
+        }
+
+        let base = self.get_quest(&quest_id).get_base_mut();
+        if let Some(SeekerStatus::GradedPendingReward(gradings)) = base.submissions.get(&seeker).cloned() {
+            base.submissions.insert(seeker, SeekerStatus::Graded(gradings));
+        }
+        broadcast(&QuestEvent::RewardsIssued { quest_id, seeker });
+    }
+
+    /// Resend the rewards that the reputation contract failed to issue.
+    fn retry_rewards(&mut self, quest_id: &QuestId, seeker: ActorId) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        // Only the seeker and the provider care about the rewards
+        let msg_src = msg::source();
+        if msg_src != seeker && msg_src != self.get_quest_ref(quest_id).get_owner() {
+            return Err(QuestError::Unauthorized);
+        }
+
+        let failed: Vec<MessageId> = self.pending_rewards
+            .iter()
+            .filter(|(_, pending)| pending.failed && &pending.quest_id == quest_id && pending.seeker == seeker)
+            .map(|(message_id, _)| *message_id)
+            .collect();
+        if failed.is_empty() {
+            return Err(QuestError::NoFailedRewards);
+        }
+
+        for message_id in failed {
+            let pending = self.pending_rewards.remove(&message_id).unwrap();
+            self.send_reward(quest_id, seeker, pending.reward);
+        }
+
+        return Ok(QuestEvent::RewardsResent { quest_id: quest_id.clone(), seeker });
+    }
+
+    /// Generate unique ids for quests, even for quests published within the same block
+    fn quest_id_gen(&mut self) -> QuestId {
+        let quest_id = self.quest_nonce.to_string();
+        self.quest_nonce += 1;
+        quest_id
     }
 
     /// Construct the base of a quest
@@ -296,10 +946,297 @@ impl Quests {
             skill_token_name: quest_info.skill_token_name,
             open_try: quest_info.open_try,
             contact_info: quest_info.contact_info,
+            max_revisions: quest_info.max_revisions,
+            rubric: quest_info.rubric,
+            prerequisites: quest_info.prerequisites,
+            milestones: quest_info.milestones,
             ..Default::default()
         }
     }
 
+    /// The seeker must have passed every prerequisite of the quest with its minimum grading.
+    fn check_prerequisites(&self, quest_id: &QuestId, seeker: ActorId) -> Result<(), QuestError> {
+        let prerequisites = &self.get_quest_ref(quest_id).get_base().prerequisites;
+        for prerequisite in prerequisites.iter() {
+            let grading = self.get_quest_ref(&prerequisite.quest_id).get_grading(seeker);
+            if !grading.is_some_and(|grading| grading.meets(&prerequisite.min_grading)) {
+                return Err(QuestError::PrerequisiteNotMet);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Check that seekers can commit to the quest.
+    fn check_open(&self, quest_id: &QuestId) -> Result<(), QuestError> {
+        match self.quest_status.get(quest_id) {
+            Some(QuestStatus::Open) => Ok(()),
+            Some(QuestStatus::Full) => Err(QuestError::CapacityFull),
+            Some(_) => Err(QuestError::QuestNotOpen),
+            None => Err(QuestError::QuestNotFound),
+        }
+    }
+
+    /// Approve a new provider, only the admin can manage providers.
+    fn add_provider(&mut self, provider: ActorId) -> Result<QuestEvent, QuestError> {
+        if msg::source() != self.admin {
+            return Err(QuestError::NotAdmin);
+        }
+        if self.is_approved(provider) {
+            return Err(QuestError::ProviderAlreadyApproved);
+        }
+
+        self.approved_providers.push(provider);
+
+        return Ok(QuestEvent::ProviderAdded { provider });
+    }
+
+    /// Revoke the approval of a provider, and optionally freeze the provider's quests.
+    /// Frozen quests are closed, so seekers who already committed can still submit and get graded.
+    fn remove_provider(&mut self, provider: ActorId, freeze_quests: bool) -> Result<QuestEvent, QuestError> {
+        if msg::source() != self.admin {
+            return Err(QuestError::NotAdmin);
+        }
+        if !self.is_approved(provider) {
+            return Err(QuestError::NotApprovedProvider);
+        }
+
+        self.approved_providers.retain(|approved| approved != &provider);
+
+        let mut frozen_quests = Vec::new();
+        if freeze_quests {
+            frozen_quests = self.quest_status
+                .iter()
+                .filter(|(quest_id, status)| {
+                    (*status == &QuestStatus::Open || *status == &QuestStatus::Full)
+                        && self.get_quest_ref(quest_id).get_owner() == provider
+                })
+                .map(|(quest_id, _)| quest_id.clone())
+                .collect();
+            for quest_id in frozen_quests.iter() {
+                self.quest_status.insert(quest_id.clone(), QuestStatus::Closed);
+                broadcast(&QuestEvent::Closed { quest_id: quest_id.clone() });
+            }
+        }
+
+        return Ok(QuestEvent::ProviderRemoved { provider, frozen_quests });
+    }
+
+    /// List all approved providers, only the admin can manage providers.
+    fn list_providers(&self) -> Result<QuestEvent, QuestError> {
+        if msg::source() != self.admin {
+            return Err(QuestError::NotAdmin);
+        }
+
+        return Ok(QuestEvent::Providers { providers: self.approved_providers.clone() });
+    }
+
+    /// Seekers appeal against a rejection within the appeal window after grading.
+    fn appeal(&mut self, quest_id: &QuestId, reason: String) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        let seeker = msg::source();
+        let base = self.get_quest_ref(quest_id).get_base();
+        if base.submissions.get(&seeker) != Some(&SeekerStatus::Graded(Gradings::Reject)) {
+            return Err(QuestError::NotRejected);
+        }
+        let graded_at = base.assessments.get(&seeker).map_or(0, |assessment| assessment.graded_at);
+        if exec::block_height() > graded_at.saturating_add(APPEAL_WINDOW) {
+            return Err(QuestError::AppealWindowClosed);
+        }
+
+        let key = (quest_id.clone(), seeker);
+        if self.appeals.contains_key(&key) {
+            return Err(QuestError::AlreadyAppealed);
+        }
+        self.appeals.insert(key, Appeal { reason, filed_at: exec::block_height(), status: AppealStatus::Pending });
+
+        return Ok(QuestEvent::Appealed { quest_id: quest_id.clone(), seeker });
+    }
+
+    /// The admin or an arbiter upholds or overturns a pending appeal.
+    /// Overturned seekers are graded again and get the rewards of the quest.
+    fn resolve_appeal(&mut self, quest_id: &QuestId, seeker: ActorId, decision: AppealDecision) -> Result<QuestEvent, QuestError> {
+        let msg_src = msg::source();
+        if msg_src != self.admin && !self.arbiters.contains(&msg_src) {
+            return Err(QuestError::NotArbiter);
+        }
+
+        let key = (quest_id.clone(), seeker);
+        match self.appeals.get(&key) {
+            Some(appeal) if appeal.status == AppealStatus::Pending => {},
+            Some(_) => return Err(QuestError::AppealNotPending),
+            None => return Err(QuestError::AppealNotFound),
+        }
+
+        let status = match decision {
+            AppealDecision::Uphold => AppealStatus::Upheld,
+            AppealDecision::Overturn(Gradings::Reject) => return Err(QuestError::InvalidOverturn),
+            AppealDecision::Overturn(gradings) => {
+                let base = self.get_quest(quest_id).get_base_mut();
+                // The graded submission is the latest one
+                let submission = base.submission_history.get(&seeker).and_then(|history| history.last()).cloned().unwrap_or_default();
+                // An overturned milestone before the last one moves the seeker on to the next milestone,
+                // and the seeker takes back the slot released by the rejection, unless it has been taken already.
+                if base.current_milestone(seeker) + 1 < base.milestones.len() {
+                    if base.capacity == 0 {
+                        return Err(QuestError::CapacityFull);
+                    }
+                    base.revisions.remove(&seeker);
+                    base.milestone_gradings.entry(seeker).or_default().push(gradings.clone());
+                    base.gradings.insert(seeker, None);
+                    base.submissions.insert(seeker, SeekerStatus::Waiting);
+                    base.capacity -= 1;
+                    let capacity = base.capacity;
+                    if capacity == 0 && self.quest_status.get(quest_id) == Some(&QuestStatus::Open) {
+                        self.quest_status.insert(quest_id.clone(), QuestStatus::Full);
+                    }
+                } else {
+                    base.gradings.insert(seeker, Some(gradings.clone()));
+                    base.submissions.insert(seeker, SeekerStatus::Graded(gradings.clone()));
+                    self.issue_rewards(quest_id, seeker, submission);
+                }
+                AppealStatus::Overturned(gradings)
+            },
+        };
+        if let Some(appeal) = self.appeals.get_mut(&key) {
+            appeal.status = status.clone();
+        }
+
+        return Ok(QuestEvent::AppealResolved { quest_id: quest_id.clone(), seeker, status });
+    }
+
+    /// Providers delegate grading and managing their quest, the roles are replaced as a whole.
+    fn set_quest_roles(&mut self, quest_id: &QuestId, graders: Vec<ActorId>, managers: Vec<ActorId>) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        let base = self.get_quest(quest_id).get_base_mut();
+        // Co-managers can't hand out roles, only the provider can
+        if msg::source() != base.provider {
+            return Err(QuestError::NotProvider);
+        }
+        base.graders = graders.clone();
+        base.managers = managers.clone();
+
+        return Ok(QuestEvent::QuestRolesSet { quest_id: quest_id.clone(), graders, managers });
+    }
+
+    /// Seekers form teams for open top-tier quests, the leader commits for the whole team.
+    fn create_team(&mut self, quest_id: &QuestId) -> Result<QuestEvent, QuestError> {
+        self.check_open(quest_id)?;
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        let leader = msg::source();
+        match quest.create_team(leader) {
+            Ok(()) => return Ok(QuestEvent::TeamCreated { quest_id: quest_id.clone(), leader }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    fn invite_to_team(&mut self, quest_id: &QuestId, member: ActorId) -> Result<QuestEvent, QuestError> {
+        self.check_open(quest_id)?;
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        let leader = msg::source();
+        match quest.invite_member(leader, member) {
+            Ok(()) => return Ok(QuestEvent::TeamInvited { quest_id: quest_id.clone(), leader, member }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    fn accept_invite(&mut self, quest_id: &QuestId, leader: ActorId) -> Result<QuestEvent, QuestError> {
+        self.check_open(quest_id)?;
+        let member = msg::source();
+        // Members share the grading of the leader, so they must have passed the prerequisites as well
+        if self.top_tier_quests.contains_key(quest_id) {
+            self.check_prerequisites(quest_id, member)?;
+        }
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::QuestNotFound),
+        };
+
+        match quest.accept_invite(member, leader) {
+            Ok(()) => return Ok(QuestEvent::TeamJoined { quest_id: quest_id.clone(), leader, member }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    /// Seekers of commit-reveal quests only publish the hash of their submission before the deadline,
+    /// so late competitors can't copy earlier submissions.
+    fn seal_submission(&mut self, quest_id: &QuestId, commitment: [u8; 32]) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        if self.quest_status.get(quest_id).unwrap() == &QuestStatus::Finished {
+            return Err(QuestError::QuestFinished);
+        }
+
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::SealingNotSupported),
+        };
+
+        let seeker = msg::source();
+        // The deadline message may still be on its way, so the deadline is checked here as well.
+        if exec::block_height() as u64 >= quest.base.current_deadline(seeker) {
+            return Err(QuestError::DeadlinePassed);
+        }
+
+        match quest.seal(seeker, commitment) {
+            Ok(()) => return Ok(QuestEvent::Sealed { quest_id: quest_id.clone(), seeker }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    /// Seekers reveal their sealed submission during the reveal window, even though the quest is finished by then.
+    fn reveal_submission(&mut self, quest_id: &QuestId, submission: SubmissionRef, salt: [u8; 32]) -> Result<QuestEvent, QuestError> {
+        // The quest must exists
+        if !self.quest_status.contains_key(quest_id) {
+            return Err(QuestError::QuestNotFound);
+        }
+
+        // Graders and the reputation NFT rely on the submission pointing to one exact artifact
+        if !submission.is_valid() {
+            return Err(QuestError::InvalidSubmission);
+        }
+
+        let quest = match self.top_tier_quests.get_mut(quest_id) {
+            Some(quest) => quest,
+            None => return Err(QuestError::SealingNotSupported),
+        };
+
+        let seeker = msg::source();
+        match quest.reveal(quest_id, seeker, submission, salt, exec::block_height() as u64) {
+            Ok(()) => return Ok(QuestEvent::Revealed { quest_id: quest_id.clone(), seeker }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    /// Replace the arbiters, only the admin can manage arbiters.
+    fn set_arbiters(&mut self, arbiters: Vec<ActorId>) -> Result<QuestEvent, QuestError> {
+        if msg::source() != self.admin {
+            return Err(QuestError::NotAdmin);
+        }
+
+        self.arbiters = arbiters.clone();
+
+        return Ok(QuestEvent::ArbitersSet { arbiters });
+    }
+
     /// Check against the approved providers list
     fn is_approved(&self, sender: ActorId) -> bool {
         self.approved_providers.contains(&sender)
@@ -323,10 +1260,29 @@ impl Quests {
             }
         }
     }
+
+    /// Return the quest to caller for read only access.
+    fn get_quest_ref(&self, quest_id: &QuestId) -> &dyn QuestTrait {
+        let quest_type = self.quests_to_tiers.get(quest_id).unwrap();
+        match quest_type {
+            QuestType::BaseTier => {
+                self.base_tier_quests.get(quest_id).unwrap()
+            },
+            QuestType::MidTier => {
+                self.mid_tier_quests.get(quest_id).unwrap()
+            },
+            QuestType::TopTier => {
+                self.top_tier_quests.get(quest_id).unwrap()
+            },
+            QuestType::Dedicated => {
+                self.dedicated_quests.get(quest_id).unwrap()
+            }
+        }
+    }
 }
 
-impl From<Quests> for State {
-    fn from(quests: Quests) -> Self {
+impl From<&Quests> for State {
+    fn from(quests: &Quests) -> Self {
         let Quests {
             admin,
             base_tier_quests,
@@ -337,54 +1293,97 @@ impl From<Quests> for State {
             approved_providers,
             quests_to_tiers,
             minumum_free_gradings,
+            reputation_contract,
+            consumed_skill_nfts,
+            skill_token_ids,
+            pending_rewards,
+            quest_nonce,
+            seeker_quests,
+            arbiters,
+            appeals,
+            unclaimed_payouts,
         } = quests;
 
         let base_tier_quests = base_tier_quests
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
         let mid_tier_quests = mid_tier_quests
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
         let top_tier_quests = top_tier_quests
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
         let dedicated_quests = dedicated_quests
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
         let quest_status = quest_status
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
         let quests_to_tiers = quests_to_tiers
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let consumed_skill_nfts = consumed_skill_nfts
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let skill_token_ids = skill_token_ids
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let pending_rewards = pending_rewards
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let seeker_quests = seeker_quests
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let appeals = appeals
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let unclaimed_payouts = unclaimed_payouts
+            .iter()
+            .map(|(k, v)| (*k, *v))
             .collect();
 
         Self {
-            admin,
+            admin: *admin,
             base_tier_quests,
             mid_tier_quests,
             top_tier_quests,
             dedicated_quests,
             quest_status,
-            approved_providers,
+            approved_providers: approved_providers.clone(),
             quests_to_tiers,
-            minumum_free_gradings,
+            minumum_free_gradings: *minumum_free_gradings,
+            reputation_contract: *reputation_contract,
+            consumed_skill_nfts,
+            skill_token_ids,
+            pending_rewards,
+            quest_nonce: *quest_nonce,
+            seeker_quests,
+            arbiters: arbiters.clone(),
+            appeals,
+            unclaimed_payouts,
         }
     }
 
 }
-
-/// Generate random id for quests
-fn quest_id_gen() -> String {
-    exec::block_timestamp().to_string()
-}
\ No newline at end of file
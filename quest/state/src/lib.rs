@@ -1,20 +1,54 @@
 #![no_std]
 use gstd::prelude::*;
+use gstd::ActorId;
 use quest_io::*;
 
 #[gmeta::metawasm]
 pub mod metafns {
     pub type State = quest_io::State;
 
-    // Return any quests that implemented the QuestTrait trait
-    pub fn get_quest_by_id(state: State, quest_id: QuestId) -> MidTierQuest {
-        for (id, quest) in state.mid_tier_quests {
-            if *id == quest_id {
-                return quest;
-            }
-        }
+    // Return the quest of any tier, tagged with its tier
+    pub fn get_quest_by_id(state: State, quest_id: QuestId) -> Option<AnyQuest> {
+        state.get_quest(&quest_id)
+    }
+
+    pub fn quest_status(state: State, quest_id: QuestId) -> Option<QuestStatus> {
+        state.quest_status
+            .into_iter()
+            .find(|(id, _)| *id == quest_id)
+            .map(|(_, status)| status)
+    }
+
+    pub fn quests_to_tiers(state: State, quest_id: QuestId) -> Option<QuestType> {
+        state.quests_to_tiers
+            .into_iter()
+            .find(|(id, _)| *id == quest_id)
+            .map(|(_, quest_type)| quest_type)
+    }
+
+    // Return a page of quests matching the filter, so the whole state is not needed
+    pub fn list_quests(state: State, query: QuestQuery) -> Vec<(QuestId, QuestStatus, AnyQuest)> {
+        state.list_quests(&query)
+    }
+
+    // Return the quests a seeker has committed to, with the seeker's status and grading in each
+    pub fn get_seeker_quests(state: State, seeker: ActorId) -> Vec<(QuestId, QuestType, SeekerStatus, Option<Gradings>)> {
+        state.get_seeker_quests(seeker)
+    }
+
+    pub fn get_appeal(state: State, quest_id: QuestId, seeker: ActorId) -> Option<Appeal> {
+        state.get_appeal(&quest_id, seeker)
+    }
+
+    pub fn pending_appeals(state: State) -> Vec<((QuestId, ActorId), Appeal)> {
+        state.appeals
+            .into_iter()
+            .filter(|(_, appeal)| appeal.status == AppealStatus::Pending)
+            .collect()
+    }
 
-        panic!("Quest not found");
+    // Return the quest and every quest it depends on, each with its direct prerequisites
+    pub fn prerequisite_graph(state: State, quest_id: QuestId) -> Vec<(QuestId, Vec<Prerequisite>)> {
+        state.get_prerequisite_graph(&quest_id)
     }
-    
 }
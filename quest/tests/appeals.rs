@@ -0,0 +1,108 @@
+use gstd::ActorId;
+use gtest::{Log, Program, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const ARBITER: u64 = 104;
+
+/// Publish a base-tier quest and reject the submission of `SEEKER`.
+fn reject(quest: &Program) -> QuestId {
+    let quest_id = publish(quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Reject),
+            feedback: String::from("Missing tests."),
+        },
+    );
+
+    quest_id
+}
+
+#[test]
+fn overturned_appeals_issue_rewards() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let quest_id = reject(&quest);
+
+    let res = quest.send(SEEKER, QuestAction::Appeal { quest_id: quest_id.clone(), reason: String::from("Tests are in the repo.") });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Appealed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+    let res = quest.send(SEEKER, QuestAction::Appeal { quest_id: quest_id.clone(), reason: String::new() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::AlreadyAppealed))));
+
+    // Only the admin and arbiters can resolve appeals
+    let decision = AppealDecision::Overturn(Gradings::Accept);
+    let res = quest.send(ARBITER, QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: decision.clone() });
+    assert!(res.contains(&Log::builder().dest(ARBITER).payload(Err::<QuestEvent, QuestError>(QuestError::NotArbiter))));
+    quest.send(ADMIN, QuestAction::SetArbiters { arbiters: vec![ARBITER.into()] });
+
+    let res = quest.send(ARBITER, QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: decision.clone() });
+    assert!(res.contains(&Log::builder().dest(ARBITER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::AppealResolved {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        status: AppealStatus::Overturned(Gradings::Accept),
+    }))));
+    let res = quest.send(ARBITER, QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision });
+    assert!(res.contains(&Log::builder().dest(ARBITER).payload(Err::<QuestEvent, QuestError>(QuestError::AppealNotPending))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert_eq!(state.get_appeal(&quest_id, SEEKER.into()).map(|appeal| appeal.status), Some(AppealStatus::Overturned(Gradings::Accept)));
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Graded(Gradings::Accept)));
+
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    assert!(balances.contains(&(ActorId::from(SEEKER), 1)));
+}
+
+#[test]
+fn upheld_appeals_keep_the_rejection() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = reject(&quest);
+
+    quest.send(SEEKER, QuestAction::Appeal { quest_id: quest_id.clone(), reason: String::from("Tests are in the repo.") });
+
+    let res = quest.send(ADMIN, QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: AppealDecision::Overturn(Gradings::Reject) });
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidOverturn))));
+    let res = quest.send(ADMIN, QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: AppealDecision::Uphold });
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::AppealResolved {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        status: AppealStatus::Upheld,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Graded(Gradings::Reject)));
+}
+
+#[test]
+fn appeals_close_after_the_window() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = reject(&quest);
+
+    sys.spend_blocks(14_401);
+    let res = quest.send(SEEKER, QuestAction::Appeal { quest_id, reason: String::from("Tests are in the repo.") });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::AppealWindowClosed))));
+}
@@ -0,0 +1,164 @@
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn top_tier_quests_close_and_finish_on_deadlines() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(
+        &quest,
+        QuestType::TopTier,
+        IncomingQuest {
+            application_deadline: 10,
+            deadline: 20,
+            ..incoming_quest()
+        },
+        PRIZE,
+    );
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    // The quest gets closed at the application deadline
+    sys.spend_blocks(10);
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::QuestNotOpen))));
+
+    // Committed seekers can't submit after the quest is finished
+    sys.spend_blocks(10);
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission_ref("late") });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::QuestFinished))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id, QuestStatus::Finished)));
+}
+
+#[test]
+fn grading_after_the_deadline_keeps_the_quest_finished() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(
+        &quest,
+        QuestType::TopTier,
+        IncomingQuest {
+            capacity: 1,
+            application_deadline: 10,
+            deadline: 20,
+            ..incoming_quest()
+        },
+        PRIZE,
+    );
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    sys.spend_blocks(20);
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+
+    // The slot released by grading doesn't reopen the quest
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::QuestNotOpen))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id, QuestStatus::Finished)));
+}
+
+#[test]
+fn deadlines_must_be_in_the_future() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.spend_blocks(5);
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest { deadline: 1, ..incoming_quest() },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidDeadline))));
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest { deadline: u64::MAX, ..incoming_quest() },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidDeadline))));
+}
+
+#[test]
+fn modified_deadlines_are_rescheduled() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Modify {
+            quest_id: quest_id.clone(),
+            base_info: Modifiable {
+                quest_name: String::from("Simulation"),
+                description: String::new(),
+                deliverables: String::new(),
+                deadline: 2_000,
+                contact_info: String::new(),
+            },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Modified { quest_id: quest_id.clone() }))));
+
+    // The message of the original deadline is ignored
+    sys.spend_blocks(1_000);
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id.clone(), QuestStatus::Open)));
+
+    // The message of the modified deadline finishes the quest
+    sys.spend_blocks(1_000);
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id, QuestStatus::Finished)));
+}
+
+#[test]
+fn only_the_contract_expires_deadlines() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(SEEKER, QuestAction::ExpireDeadline { quest_id: quest_id.clone(), deadline: Deadline::Final });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::Unauthorized))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id, QuestStatus::Open)));
+}
@@ -0,0 +1,82 @@
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+/// Off-chain indexers listen to messages sent to `ActorId::zero()`.
+const BROADCAST: u64 = 0;
+
+#[test]
+fn lifecycle_events_are_broadcast() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let submission = submission_ref("submission");
+
+    let res = quest.send(PROVIDER, QuestAction::Publish { quest_type: QuestType::BaseTier, quest_info: incoming_quest() });
+    let quest_id: QuestId = match decode_reply::<Result<QuestEvent, QuestError>>(&res, PROVIDER) {
+        Ok(QuestEvent::Published { quest_id, .. }) => quest_id,
+        other => panic!("Failed to publish: {:?}", other),
+    };
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Published {
+        quest_id: quest_id.clone(),
+        quest_type: QuestType::BaseTier,
+        provider: PROVIDER.into(),
+    })));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Committed { quest_id: quest_id.clone(), seeker: SEEKER.into() })));
+
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Submitted { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Submitted { quest_id: quest_id.clone(), seeker: SEEKER.into() })));
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Reject),
+            feedback: String::new(),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Reject,
+    })));
+
+    let res = quest.send(PROVIDER, QuestAction::Close { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Closed { quest_id: quest_id.clone() }))));
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Closed { quest_id })));
+}
+
+#[test]
+fn failed_actions_are_not_broadcast() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let res = quest.send(SEEKER, QuestAction::Publish { quest_type: QuestType::BaseTier, quest_info: incoming_quest() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotApprovedProvider))));
+    assert!(!res.contains(&Log::builder().dest(BROADCAST)));
+}
+
+#[test]
+fn frozen_quests_are_broadcast_as_closed() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(ADMIN, QuestAction::RemoveProvider { provider: PROVIDER.into(), freeze_quests: true });
+    assert!(res.contains(&Log::builder().dest(BROADCAST).payload(QuestEvent::Closed { quest_id })));
+}
@@ -0,0 +1,241 @@
+use gstd::ActorId;
+use gtest::{Log, Program, RunResult, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+fn milestones() -> Vec<Milestone> {
+    vec![
+        Milestone { deliverables: String::from("A design document."), deadline: 300 },
+        Milestone { deliverables: String::from("A link to the source code."), deadline: 600 },
+    ]
+}
+
+/// Submit and get graded by `PROVIDER` for the current milestone.
+fn grade(quest: &Program, quest_id: &QuestId, submission: &str, grading: Gradings) -> RunResult {
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission_ref(submission) });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission: submission_ref(submission),
+            grading: Evaluation::Gradings(grading),
+            feedback: String::new(),
+        },
+    )
+}
+
+#[test]
+fn seekers_advance_through_milestones() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let skill_nft = mint_skill_nft(&reputation, SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { milestones: milestones(), ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(skill_nft), passcode: None });
+
+    let res = grade(&quest, &quest_id, "design", Gradings::Accept);
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::MilestonePassed {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        milestone: 0,
+        grading: Gradings::Accept,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Waiting));
+    assert_eq!(mid_tier_quest.base.current_deadline(SEEKER.into()), 600);
+    assert_eq!(mid_tier_quest.base.capacity, 9);
+
+    // Nothing is rewarded before the last milestone
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    assert!(!state.balances.iter().any(|(id, balances)| *id == skill_ft && balances.iter().any(|(owner, _)| *owner == ActorId::from(SEEKER))));
+
+    let res = grade(&quest, &quest_id, "source", Gradings::Good);
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Good,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.milestone_gradings.get(&ActorId::from(SEEKER)), Some(&vec![Gradings::Accept]));
+    assert_eq!(mid_tier_quest.base.gradings.get(&ActorId::from(SEEKER)), Some(&Some(Gradings::Good)));
+    assert_eq!(mid_tier_quest.base.capacity, 10);
+
+    // The skill token is issued once for the whole quest, not for every milestone
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    assert!(balances.contains(&(ActorId::from(SEEKER), 1)));
+}
+
+#[test]
+fn rejected_milestones_end_the_quest() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { milestones: milestones(), ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+
+    let res = grade(&quest, &quest_id, "design", Gradings::Reject);
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Reject,
+    }))));
+
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id, submission: submission_ref("source") });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotCommitted))));
+}
+
+#[test]
+fn overturned_milestones_move_seekers_on() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let skill_nft = mint_skill_nft(&reputation, SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { milestones: milestones(), ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(skill_nft), passcode: None });
+    grade(&quest, &quest_id, "design", Gradings::Reject);
+    quest.send(SEEKER, QuestAction::Appeal { quest_id: quest_id.clone(), reason: String::from("The design document is complete.") });
+
+    let res = quest.send(
+        ADMIN,
+        QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: AppealDecision::Overturn(Gradings::Accept) },
+    );
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::AppealResolved {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        status: AppealStatus::Overturned(Gradings::Accept),
+    }))));
+
+    // The seeker is back on the quest, working on the last milestone
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Waiting));
+    assert_eq!(mid_tier_quest.base.milestone_gradings.get(&ActorId::from(SEEKER)), Some(&vec![Gradings::Accept]));
+    assert_eq!(mid_tier_quest.base.current_deadline(SEEKER.into()), 600);
+    assert_eq!(mid_tier_quest.base.capacity, 9);
+
+    let res = grade(&quest, &quest_id, "source", Gradings::Good);
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Good,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.gradings.get(&ActorId::from(SEEKER)), Some(&Some(Gradings::Good)));
+    assert_eq!(mid_tier_quest.base.capacity, 10);
+}
+
+#[test]
+fn overturns_need_the_released_slot() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let skill_nft = mint_skill_nft(&reputation, SEEKER);
+    let another_skill_nft = mint_skill_nft(&reputation, ANOTHER_SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { capacity: 1, milestones: milestones(), ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(skill_nft), passcode: None });
+    grade(&quest, &quest_id, "design", Gradings::Reject);
+    quest.send(SEEKER, QuestAction::Appeal { quest_id: quest_id.clone(), reason: String::from("The design document is complete.") });
+
+    // The slot released by the rejection is taken by another seeker
+    quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(another_skill_nft), passcode: None });
+
+    let res = quest.send(
+        ADMIN,
+        QuestAction::ResolveAppeal { quest_id: quest_id.clone(), seeker: SEEKER.into(), decision: AppealDecision::Overturn(Gradings::Accept) },
+    );
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Err::<QuestEvent, QuestError>(QuestError::CapacityFull))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Graded(Gradings::Reject)));
+    assert_eq!(mid_tier_quest.base.capacity, 0);
+}
+
+#[test]
+fn milestone_deadlines_are_enforced() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { milestones: milestones(), ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    sys.spend_blocks(300);
+
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id, submission: submission_ref("design") });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::DeadlinePassed))));
+
+    // Milestones must end by the quest deadline
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest {
+                milestones: vec![Milestone { deliverables: String::from("A link to the source code."), deadline: 2_000 }],
+                ..incoming_quest()
+            },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidMilestones))));
+
+    // Modifying can't move the deadline before the last milestone either
+    let milestones = vec![
+        Milestone { deliverables: String::from("A design document."), deadline: 700 },
+        Milestone { deliverables: String::from("A link to the source code."), deadline: 900 },
+    ];
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { milestones, ..incoming_quest() });
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Modify {
+            quest_id,
+            base_info: Modifiable {
+                quest_name: String::from("Simulation"),
+                description: String::new(),
+                deliverables: String::new(),
+                deadline: 800,
+                contact_info: String::new(),
+            },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidMilestones))));
+
+    // Every milestone index must fit into `MilestonePassed`
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest {
+                milestones: (0..=MAX_MILESTONES as u64)
+                    .map(|i| Milestone { deliverables: String::from("A progress report."), deadline: 400 + i })
+                    .collect(),
+                ..incoming_quest()
+            },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidMilestones))));
+}
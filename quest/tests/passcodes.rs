@@ -0,0 +1,50 @@
+use gtest::{Log, System};
+use quest_io::helper_functions::hash_passcode;
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const SALT: [u8; 32] = [7; 32];
+
+#[test]
+fn passcodes_gate_dedicated_quests() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(
+        &quest,
+        QuestType::Dedicated,
+        IncomingQuest { passcode_hash: Some(hash_passcode(&SALT, "CS101-fall")), passcode_salt: SALT, ..incoming_quest() },
+    );
+    // The same passcode hashes differently under another salt
+    assert_ne!(hash_passcode(&SALT, "CS101-fall"), hash_passcode(&[0; 32], "CS101-fall"));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::IncorrectPasscode))));
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: Some(String::from("CS101-spring")) });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::IncorrectPasscode))));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: Some(String::from("CS101-fall")) });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id, seeker: SEEKER.into() }))));
+}
+
+#[test]
+fn only_dedicated_quests_take_passcodes() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest { passcode_hash: Some(hash_passcode(&SALT, "CS101-fall")), passcode_salt: SALT, ..incoming_quest() },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::PasscodeNotSupported))));
+}
@@ -0,0 +1,104 @@
+use gtest::{Log, Program, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+/// Commit, submit and get graded by `PROVIDER` in the quest.
+fn pass(quest: &Program, quest_id: &QuestId, seeker: u64, grading: Gradings) {
+    let submission = submission_ref("submission");
+
+    quest.send(seeker, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(seeker, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: seeker.into(),
+            submission,
+            grading: Evaluation::Gradings(grading),
+            feedback: String::new(),
+        },
+    );
+}
+
+#[test]
+fn prerequisites_are_enforced_on_commit() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let intro = publish(&quest, QuestType::BaseTier);
+    let prerequisites = vec![Prerequisite { quest_id: intro.clone(), min_grading: Gradings::Accept }];
+    let simulation = publish_with(&quest, QuestType::BaseTier, IncomingQuest { prerequisites: prerequisites.clone(), ..incoming_quest() });
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: simulation.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::PrerequisiteNotMet))));
+
+    pass(&quest, &intro, SEEKER, Gradings::Good);
+    pass(&quest, &intro, ANOTHER_SEEKER, Gradings::Reject);
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: simulation.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: simulation.clone(), seeker: SEEKER.into() }))));
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: simulation.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::PrerequisiteNotMet))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert_eq!(state.get_prerequisite_graph(&simulation), vec![(simulation, prerequisites), (intro, vec![])]);
+}
+
+#[test]
+fn prerequisites_must_exist() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest {
+                prerequisites: vec![Prerequisite { quest_id: String::from("42"), min_grading: Gradings::Accept }],
+                ..incoming_quest()
+            },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidPrerequisite))));
+}
+
+#[test]
+fn team_members_must_meet_the_prerequisites() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let intro = publish(&quest, QuestType::BaseTier);
+    let prerequisites = vec![Prerequisite { quest_id: intro.clone(), min_grading: Gradings::Accept }];
+    sys.mint_to(PROVIDER, PRIZE);
+    let capstone = publish_with_value(
+        &quest,
+        QuestType::TopTier,
+        IncomingQuest { prerequisites, max_team_size: 2, ..incoming_quest() },
+        PRIZE,
+    );
+
+    pass(&quest, &intro, SEEKER, Gradings::Good);
+    quest.send(SEEKER, QuestAction::CreateTeam { quest_id: capstone.clone() });
+    quest.send(SEEKER, QuestAction::InviteToTeam { quest_id: capstone.clone(), member: ANOTHER_SEEKER.into() });
+
+    // Members share the leader's grading, so they can't skip the prerequisites by joining a team
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::AcceptInvite { quest_id: capstone.clone(), leader: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::PrerequisiteNotMet))));
+
+    pass(&quest, &intro, ANOTHER_SEEKER, Gradings::Accept);
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::AcceptInvite { quest_id: capstone.clone(), leader: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::TeamJoined {
+        quest_id: capstone,
+        leader: SEEKER.into(),
+        member: ANOTHER_SEEKER.into(),
+    }))));
+}
@@ -0,0 +1,119 @@
+use gstd::ActorId;
+use gtest::{Log, Program, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+/// Commit, submit and grade `seeker` in the given quest.
+fn pass(quest: &Program, quest_id: &QuestId, seeker: u64) {
+    let submission = submission_ref("submission");
+    quest.send(seeker, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(seeker, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: seeker.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+}
+
+#[test]
+fn top_tier_quests_require_a_prize() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let res = quest.send(PROVIDER, QuestAction::Publish { quest_type: QuestType::TopTier, quest_info: incoming_quest() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::PrizeRequired))));
+
+    sys.mint_to(PROVIDER, PRIZE);
+    let res = quest.send_with_value(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::TopTier,
+            quest_info: IncomingQuest { prize_split: vec![60, 30], ..incoming_quest() },
+        },
+        PRIZE,
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidPrizeSplit))));
+}
+
+#[test]
+fn prize_is_split_among_ranked_winners() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, incoming_quest(), PRIZE);
+
+    pass(&quest, &quest_id, SEEKER);
+
+    // Seekers who have not passed can't win
+    let res = quest.send(PROVIDER, QuestAction::AssignWinners { quest_id: quest_id.clone(), winners: vec![ANOTHER_SEEKER.into()] });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::WinnerNotPassed))));
+
+    let res = quest.send(PROVIDER, QuestAction::AssignWinners { quest_id: quest_id.clone(), winners: vec![SEEKER.into()] });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::WinnersAssigned {
+        quest_id: quest_id.clone(),
+        payouts: vec![(SEEKER.into(), PRIZE * 60 / 100)],
+    }))));
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(QuestEvent::PrizePaid { quest_id: quest_id.clone(), winner: SEEKER.into(), amount: PRIZE * 60 / 100 })));
+
+    let res = quest.send(PROVIDER, QuestAction::AssignWinners { quest_id: quest_id.clone(), winners: vec![SEEKER.into()] });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::PrizeFinalized))));
+
+    // The unclaimed 2nd place can only be refunded once the quest is finished
+    let res = quest.send(PROVIDER, QuestAction::RefundPrize { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::QuestNotFinished))));
+
+    sys.spend_blocks(1_000);
+    let res = quest.send(PROVIDER, QuestAction::RefundPrize { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::PrizeRefunded {
+        quest_id: quest_id.clone(),
+        provider: PROVIDER.into(),
+        amount: PRIZE * 40 / 100,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, top_tier_quest) = state.top_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(top_tier_quest.winners, vec![ActorId::from(SEEKER)]);
+    assert_eq!(top_tier_quest.prize_balance, 0);
+
+    // Every payout went through, so nothing is kept to claim
+    assert!(state.unclaimed_payouts.is_empty());
+    let res = quest.send(SEEKER, QuestAction::ClaimPayout);
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NothingToClaim))));
+}
+
+#[test]
+fn prize_is_kept_for_winners_until_the_grace_period_ends() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, incoming_quest(), PRIZE);
+
+    // The quest is finished, but no winners are assigned yet
+    sys.spend_blocks(1_000);
+    let res = quest.send(PROVIDER, QuestAction::RefundPrize { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::PrizeNotRefundable))));
+
+    sys.spend_blocks(PRIZE_GRACE_PERIOD as u32);
+    let res = quest.send(PROVIDER, QuestAction::RefundPrize { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::PrizeRefunded {
+        quest_id,
+        provider: PROVIDER.into(),
+        amount: PRIZE,
+    }))));
+}
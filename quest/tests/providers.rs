@@ -0,0 +1,69 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const NEW_PROVIDER: u64 = 104;
+
+#[test]
+fn admin_manages_providers() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let res = quest.send(PROVIDER, QuestAction::AddProvider { provider: NEW_PROVIDER.into() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::NotAdmin))));
+
+    let res = quest.send(ADMIN, QuestAction::AddProvider { provider: NEW_PROVIDER.into() });
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::ProviderAdded { provider: NEW_PROVIDER.into() }))));
+
+    let res = quest.send(ADMIN, QuestAction::ListProviders);
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Providers {
+        providers: vec![ActorId::from(PROVIDER), ActorId::from(NEW_PROVIDER)],
+    }))));
+}
+
+#[test]
+fn removing_a_provider_can_freeze_their_quests() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(ADMIN, QuestAction::RemoveProvider { provider: PROVIDER.into(), freeze_quests: true });
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::ProviderRemoved {
+        provider: PROVIDER.into(),
+        frozen_quests: vec![quest_id.clone()],
+    }))));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id, skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::QuestNotOpen))));
+
+    let res = quest.send(PROVIDER, QuestAction::Publish { quest_type: QuestType::BaseTier, quest_info: incoming_quest() });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::NotApprovedProvider))));
+}
+
+#[test]
+fn removing_a_provider_can_leave_their_quests_running() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(ADMIN, QuestAction::RemoveProvider { provider: PROVIDER.into(), freeze_quests: false });
+    assert!(res.contains(&Log::builder().dest(ADMIN).payload(Ok::<QuestEvent, QuestError>(QuestEvent::ProviderRemoved {
+        provider: PROVIDER.into(),
+        frozen_quests: vec![],
+    }))));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id, seeker: SEEKER.into() }))));
+}
@@ -0,0 +1,25 @@
+use gtest::System;
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn quests_published_in_one_block_get_distinct_ids() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let quest_ids: Vec<QuestId> = (0..3).map(|_| publish(&quest, QuestType::BaseTier)).collect();
+    assert_ne!(quest_ids[0], quest_ids[1]);
+    assert_ne!(quest_ids[1], quest_ids[2]);
+    assert_ne!(quest_ids[0], quest_ids[2]);
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert_eq!(state.base_tier_quests.len(), 3);
+    for quest_id in quest_ids {
+        assert!(state.quests_to_tiers.contains(&(quest_id, QuestType::BaseTier)));
+    }
+}
@@ -0,0 +1,92 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn seekers_resubmit_until_revisions_run_out() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { max_revisions: 1, ..incoming_quest() });
+    let first = submission_ref("first");
+    let second = submission_ref("second");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: first.clone() });
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::RequestRevision {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission: first.clone(),
+            feedback: String::from("Add tests."),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::RevisionRequested {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        feedback: String::from("Add tests."),
+        remaining_rounds: 0,
+    }))));
+
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: second.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Submitted { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::RequestRevision {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission: second.clone(),
+            feedback: String::from("Add more tests."),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::NoRevisionsLeft))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(base_tier_quest.base.submission_history.get(&ActorId::from(SEEKER)), Some(&vec![first, second.clone()]));
+    assert_eq!(base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Submitted(second)));
+}
+
+#[test]
+fn only_the_latest_submission_can_be_revised() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { max_revisions: 2, ..incoming_quest() });
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    let res = quest.send(
+        SEEKER,
+        QuestAction::RequestRevision {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission: submission.clone(),
+            feedback: String::new(),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotGrader))));
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::RequestRevision {
+            quest_id,
+            commiter: SEEKER.into(),
+            submission: submission_ref("outdated"),
+            feedback: String::new(),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::NotSubmitted))));
+}
@@ -0,0 +1,156 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn passed_seekers_receive_skill_tokens_and_nfts() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let skill_nft = mint_skill_nft(&reputation, SEEKER);
+    let quest_id = publish(&quest, QuestType::MidTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(skill_nft), passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission: submission.clone(),
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let seeker: ActorId = SEEKER.into();
+
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    assert!(balances.contains(&(seeker, 1)));
+
+    let (_, nfts) = state.nft_owners.iter().find(|(owner, _)| *owner == seeker).unwrap();
+    assert_eq!(nfts.len(), 2);
+    let reward = state
+        .skill_nft_metadata
+        .iter()
+        .find(|(id, _)| *id != skill_nft && nfts.contains(id))
+        .map(|(_, metadata)| metadata)
+        .unwrap();
+    assert_eq!(reward.title, Some(String::from(REPUTATION_NFT_NAME)));
+    let details = reward.quest_details.as_ref().unwrap();
+    assert_eq!(details.id, Some(quest_id));
+    assert_eq!(details.submission, Some(submission));
+}
+
+#[test]
+fn rejected_seekers_receive_nothing() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id,
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Reject),
+            feedback: String::new(),
+        },
+    );
+
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    assert!(balances.is_empty());
+}
+
+#[test]
+fn seekers_are_graded_once_rewards_are_confirmed() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Good),
+            feedback: String::new(),
+        },
+    );
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(
+        base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)),
+        Some(&SeekerStatus::Graded(Gradings::Good))
+    );
+    assert!(state.pending_rewards.is_empty());
+}
+
+#[test]
+fn failed_rewards_keep_seekers_pending() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    // The skill FT does not exist in the reputation contract, so minting fails
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, 42)]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::RetryRewards { quest_id: quest_id.clone(), seeker: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::Unauthorized))));
+
+    let res = quest.send(SEEKER, QuestAction::RetryRewards { quest_id: quest_id.clone(), seeker: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::RewardsResent { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(
+        base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)),
+        Some(&SeekerStatus::GradedPendingReward(Gradings::Accept))
+    );
+    assert_eq!(state.pending_rewards.len(), 1);
+    let (_, pending) = &state.pending_rewards[0];
+    assert_eq!(pending.reward, Reward::SkillToken(42));
+    assert!(pending.failed);
+}
@@ -0,0 +1,75 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const GRADER: u64 = 104;
+const MANAGER: u64 = 105;
+
+#[test]
+fn graders_grade_and_managers_close() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    let res = quest.send(PROVIDER, QuestAction::SetQuestRoles { quest_id: quest_id.clone(), graders: vec![GRADER.into()], managers: vec![MANAGER.into()] });
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::QuestRolesSet {
+        quest_id: quest_id.clone(),
+        graders: vec![GRADER.into()],
+        managers: vec![MANAGER.into()],
+    }))));
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    let grade = |grader: u64| {
+        quest.send(
+            grader,
+            QuestAction::Grade {
+                quest_id: quest_id.clone(),
+                commiter: SEEKER.into(),
+                submission: submission.clone(),
+                grading: Evaluation::Gradings(Gradings::Reject),
+                feedback: String::new(),
+            },
+        )
+    };
+    let res = grade(MANAGER);
+    assert!(res.contains(&Log::builder().dest(MANAGER).payload(Err::<QuestEvent, QuestError>(QuestError::NotGrader))));
+    let res = grade(GRADER);
+    assert!(res.contains(&Log::builder().dest(GRADER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Reject,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(base_tier_quest.base.assessments.get(&ActorId::from(SEEKER)).map(|assessment| assessment.grader), Some(ActorId::from(GRADER)));
+
+    let res = quest.send(GRADER, QuestAction::Close { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(GRADER).payload(Err::<QuestEvent, QuestError>(QuestError::NotManager))));
+    let res = quest.send(MANAGER, QuestAction::Close { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(MANAGER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Closed { quest_id }))));
+}
+
+#[test]
+fn only_the_provider_assigns_roles() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    quest.send(PROVIDER, QuestAction::SetQuestRoles { quest_id: quest_id.clone(), graders: vec![], managers: vec![MANAGER.into()] });
+
+    let res = quest.send(MANAGER, QuestAction::SetQuestRoles { quest_id, graders: vec![MANAGER.into()], managers: vec![] });
+    assert!(res.contains(&Log::builder().dest(MANAGER).payload(Err::<QuestEvent, QuestError>(QuestError::NotProvider))));
+}
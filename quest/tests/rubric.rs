@@ -0,0 +1,120 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+fn rubric() -> Rubric {
+    Rubric {
+        criteria: vec![
+            Criterion { name: String::from("Correctness"), weight: 2, max_score: 10 },
+            Criterion { name: String::from("Style"), weight: 1, max_score: 10 },
+        ],
+        pass_threshold: 50,
+        good_threshold: 80,
+    }
+}
+
+#[test]
+fn scores_map_onto_gradings() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish_with(&quest, QuestType::BaseTier, IncomingQuest { rubric: Some(rubric()), ..incoming_quest() });
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    let grade = |grading: Evaluation| {
+        quest.send(
+            PROVIDER,
+            QuestAction::Grade {
+                quest_id: quest_id.clone(),
+                commiter: SEEKER.into(),
+                submission: submission.clone(),
+                grading,
+                feedback: String::from("Clean code, missing edge cases."),
+            },
+        )
+    };
+
+    let res = grade(Evaluation::Gradings(Gradings::Good));
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::ScoresRequired))));
+    let res = grade(Evaluation::Scores(vec![8]));
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidScores))));
+    let res = grade(Evaluation::Scores(vec![8, 11]));
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidScores))));
+
+    // (2 * 80% + 1 * 50%) / 3 = 70%, between the pass and good thresholds
+    let res = grade(Evaluation::Scores(vec![8, 5]));
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Accept,
+    }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(
+        base_tier_quest.base.assessments.get(&ActorId::from(SEEKER)),
+        Some(&Assessment {
+            scores: vec![8, 5],
+            overall_score: Some(7_000),
+            feedback: String::from("Clean code, missing edge cases."),
+        }),
+    );
+}
+
+#[test]
+fn rubrics_are_validated_at_publish() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    for rubric in [
+        Rubric { criteria: vec![], ..rubric() },
+        Rubric { pass_threshold: 90, ..rubric() },
+        Rubric { criteria: vec![Criterion { name: String::from("Correctness"), weight: 1, max_score: 0 }], ..rubric() },
+    ] {
+        let res = quest.send(
+            PROVIDER,
+            QuestAction::Publish {
+                quest_type: QuestType::BaseTier,
+                quest_info: IncomingQuest { rubric: Some(rubric), ..incoming_quest() },
+            },
+        );
+        assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidRubric))));
+    }
+}
+
+#[test]
+fn quests_without_rubric_are_graded_directly() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id,
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Scores(vec![10, 10]),
+            feedback: String::new(),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::ScoresNotAccepted))));
+}
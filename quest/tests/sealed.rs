@@ -0,0 +1,154 @@
+use gtest::{Log, System};
+use quest_io::helper_functions::seal_submission;
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const SALT: [u8; 32] = [7; 32];
+
+#[test]
+fn sealed_submissions_are_graded_once_revealed() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { reveal_window: 100, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::SealRequired))));
+
+    let commitment = seal_submission(&quest_id, SEEKER.into(), &submission, &SALT);
+    let res = quest.send(SEEKER, QuestAction::SealSubmission { quest_id: quest_id.clone(), commitment });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Sealed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let reveal = |salt: [u8; 32]| quest.send(SEEKER, QuestAction::RevealSubmission { quest_id: quest_id.clone(), submission: submission.clone(), salt });
+    let grade = || {
+        quest.send(
+            PROVIDER,
+            QuestAction::Grade {
+                quest_id: quest_id.clone(),
+                commiter: SEEKER.into(),
+                submission: submission.clone(),
+                grading: Evaluation::Gradings(Gradings::Accept),
+                feedback: String::new(),
+            },
+        )
+    };
+
+    // Nothing can be revealed nor graded before the deadline
+    let res = reveal(SALT);
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotInRevealWindow))));
+    let res = grade();
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::NotSubmitted))));
+
+    sys.spend_blocks(1_000);
+    let res = reveal([8; 32]);
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::CommitmentMismatch))));
+    let res = reveal(SALT);
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Revealed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let res = grade();
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id: quest_id.clone(),
+        seeker: SEEKER.into(),
+        grading: Gradings::Accept,
+    }))));
+}
+
+#[test]
+fn revealed_submissions_cannot_be_replayed() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { reveal_window: 100, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("submission");
+    let commitment = seal_submission(&quest_id, SEEKER.into(), &submission, &SALT);
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::SealSubmission { quest_id: quest_id.clone(), commitment });
+
+    // The public commitment of another seeker can be copied, but it is bound to that seeker
+    quest.send(ANOTHER_SEEKER, QuestAction::SealSubmission { quest_id: quest_id.clone(), commitment });
+
+    sys.spend_blocks(1_000);
+    quest.send(SEEKER, QuestAction::RevealSubmission { quest_id: quest_id.clone(), submission: submission.clone(), salt: SALT });
+
+    // Once revealed, the submission and salt don't match a commitment of any other seeker
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::RevealSubmission { quest_id, submission, salt: SALT });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::CommitmentMismatch))));
+}
+
+#[test]
+fn reveals_close_with_the_reveal_window() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { reveal_window: 100, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("submission");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::SealSubmission { quest_id: quest_id.clone(), commitment: seal_submission(&quest_id, SEEKER.into(), &submission, &SALT) });
+
+    sys.spend_blocks(1_100);
+    let res = quest.send(SEEKER, QuestAction::RevealSubmission { quest_id, submission, salt: SALT });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotInRevealWindow))));
+
+    // Only top-tier quests take sealed submissions
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest { reveal_window: 100, deadline: 2_000, ..incoming_quest() },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::SealingNotSupported))));
+}
+
+#[test]
+fn sealed_submissions_cannot_be_revised() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, 2 * PRIZE);
+
+    // A revision would be due after the deadline it was revealed at, so it could never be sealed again
+    let res = quest.send_with_value(
+        PROVIDER,
+        QuestAction::Publish { quest_type: QuestType::TopTier, quest_info: IncomingQuest { reveal_window: 100, max_revisions: 1, ..incoming_quest() } },
+        PRIZE,
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::RevisionsNotSupported))));
+
+    let milestones = vec![
+        Milestone { deliverables: String::from("A design document."), deadline: 300 },
+        Milestone { deliverables: String::from("A link to the source code."), deadline: 600 },
+    ];
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { reveal_window: 100, milestones, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("design");
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::SealSubmission { quest_id: quest_id.clone(), commitment: seal_submission(&quest_id, SEEKER.into(), &submission, &SALT) });
+
+    // The first milestone is revealed while the quest is still running
+    sys.spend_blocks(300);
+    quest.send(SEEKER, QuestAction::RevealSubmission { quest_id: quest_id.clone(), submission: submission.clone(), salt: SALT });
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::RequestRevision { quest_id, commiter: SEEKER.into(), submission, feedback: String::from("Add a sequence diagram.") },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::RevisionsNotSupported))));
+}
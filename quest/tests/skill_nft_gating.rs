@@ -0,0 +1,89 @@
+use gtest::{Log, System};
+use quest_io::*;
+use reputation_io::MTKAction;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn commit_with_skill_nft() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let token_id = mint_skill_nft(&reputation, SEEKER);
+    let quest_id = publish(&quest, QuestType::MidTier);
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(token_id), passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id, seeker: SEEKER.into() }))));
+}
+
+#[test]
+fn commit_without_skill_nft() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let token_id = mint_skill_nft(&reputation, SEEKER);
+    let quest_id = publish(&quest, QuestType::MidTier);
+
+    // No skill NFT is given
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::SkillNftRequired))));
+
+    // The skill NFT belongs to someone else
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id, skill_nft: Some(token_id), passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::SkillNftNotFound))));
+}
+
+#[test]
+fn commit_with_skill_nft_of_another_skill() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    reputation.send(ADMIN, MTKAction::AddNft { name: String::from("Simulation") });
+    let simulation_nft = mint_nft(&reputation, SEEKER, "Simulation");
+    let reputation_nft = mint_nft(&reputation, SEEKER, REPUTATION_NFT_NAME);
+    let quest_id = publish(&quest, QuestType::MidTier);
+
+    // The quest requires a Python skill NFT
+    for token_id in [simulation_nft, reputation_nft] {
+        let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(token_id), passcode: None });
+        assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::SkillNftNotFound))));
+    }
+}
+
+#[test]
+fn skill_nft_is_consumed_after_commit() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let token_id = mint_skill_nft(&reputation, SEEKER);
+    let first_quest = publish(&quest, QuestType::MidTier);
+    let second_quest = publish(&quest, QuestType::MidTier);
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: first_quest.clone(), skill_nft: Some(token_id), passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: first_quest, seeker: SEEKER.into() }))));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: second_quest, skill_nft: Some(token_id), passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::SkillNftConsumed))));
+}
+
+#[test]
+fn other_tiers_are_not_gated() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id, seeker: SEEKER.into() }))));
+}
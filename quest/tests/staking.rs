@@ -0,0 +1,84 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const STAKE: u128 = 1_000_000_000_000;
+const THIRD_SEEKER: u64 = 104;
+
+#[test]
+fn stake_is_escrowed_once_free_gradings_run_out() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let first_nft = mint_skill_nft(&reputation, SEEKER);
+    let second_nft = mint_skill_nft(&reputation, ANOTHER_SEEKER);
+    let quest_id = publish_with(
+        &quest,
+        QuestType::MidTier,
+        IncomingQuest {
+            free_gradings: 1,
+            stake: STAKE,
+            ..incoming_quest()
+        },
+    );
+    sys.mint_to(SEEKER, STAKE);
+    sys.mint_to(ANOTHER_SEEKER, STAKE);
+
+    // Free gradings are used first
+    let res = quest.send_with_value(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(first_nft), passcode: None }, STAKE);
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::StakeNotRequired))));
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(first_nft), passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    // Then the exact stake is required
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(second_nft), passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::IncorrectStake))));
+    let res = quest.send_with_value(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(second_nft), passcode: None }, STAKE);
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id.clone(), seeker: ANOTHER_SEEKER.into() }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.stakes.get(&ActorId::from(ANOTHER_SEEKER)), Some(&STAKE));
+    assert!(!mid_tier_quest.base.stakes.contains_key(&ActorId::from(SEEKER)));
+}
+
+#[test]
+fn stakes_are_refunded_on_submission_and_forfeited_at_the_deadline() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let first_nft = mint_skill_nft(&reputation, SEEKER);
+    let second_nft = mint_skill_nft(&reputation, ANOTHER_SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { free_gradings: 1, stake: STAKE, ..incoming_quest() });
+    sys.mint_to(ANOTHER_SEEKER, STAKE);
+    sys.mint_to(THIRD_SEEKER, STAKE);
+    let third_nft = mint_skill_nft(&reputation, THIRD_SEEKER);
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(first_nft), passcode: None });
+    quest.send_with_value(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(second_nft), passcode: None }, STAKE);
+    quest.send_with_value(THIRD_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(third_nft), passcode: None }, STAKE);
+
+    // Submitting sends the stake back
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission_ref("submission") });
+    let refund = Log::builder().dest(ANOTHER_SEEKER).payload(QuestEvent::StakeRefunded { quest_id: quest_id.clone(), seeker: ANOTHER_SEEKER.into(), amount: STAKE });
+    assert!(res.contains(&refund));
+    let balance = sys.balance_of(ANOTHER_SEEKER);
+    sys.get_mailbox(ANOTHER_SEEKER).claim_value(refund);
+    assert_eq!(sys.balance_of(ANOTHER_SEEKER), balance + STAKE);
+
+    // The stake of the seeker who never submitted goes to the provider at the deadline
+    sys.spend_blocks(1_000);
+    let forfeit = Log::builder().dest(PROVIDER).payload(QuestEvent::StakesForfeited { quest_id, provider: PROVIDER.into(), amount: STAKE });
+    let mailbox = sys.get_mailbox(PROVIDER);
+    assert!(mailbox.contains(&forfeit));
+    let balance = sys.balance_of(PROVIDER);
+    mailbox.claim_value(forfeit);
+    assert_eq!(sys.balance_of(PROVIDER), balance + STAKE);
+}
@@ -0,0 +1,118 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn reading_state_keeps_the_program_working() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let quest_id = publish(&quest, QuestType::BaseTier);
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id.clone(), QuestStatus::Open)));
+
+    let res = quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, base_tier_quest) = state.base_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(base_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Waiting));
+}
+
+#[test]
+fn quests_of_any_tier_can_be_looked_up() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+
+    let base_tier = publish(&quest, QuestType::BaseTier);
+    let top_tier = publish_with_value(&quest, QuestType::TopTier, incoming_quest(), PRIZE);
+    let dedicated = publish(&quest, QuestType::Dedicated);
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(matches!(state.get_quest(&base_tier), Some(AnyQuest::BaseTier(_))));
+    assert!(matches!(state.get_quest(&top_tier), Some(AnyQuest::TopTier(_))));
+    assert!(matches!(state.get_quest(&dedicated), Some(AnyQuest::Dedicated(_))));
+    assert_eq!(state.get_quest(&dedicated).unwrap().get_quest_type(), QuestType::Dedicated);
+    assert_eq!(state.get_quest(&String::from("unknown")), None);
+}
+
+#[test]
+fn quests_are_listed_by_filter_and_page() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+
+    let late = publish_with(&quest, QuestType::BaseTier, IncomingQuest { deadline: 300, ..incoming_quest() });
+    let early = publish_with(&quest, QuestType::BaseTier, IncomingQuest { deadline: 100, ..incoming_quest() });
+    let middle = publish_with(&quest, QuestType::Dedicated, IncomingQuest { deadline: 200, ..incoming_quest() });
+    quest.send(PROVIDER, QuestAction::Close { quest_id: middle.clone() });
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let ids = |query: QuestQuery| -> Vec<QuestId> { state.list_quests(&query).into_iter().map(|(id, _, _)| id).collect() };
+
+    assert_eq!(ids(QuestQuery { limit: 10, ..Default::default() }), vec![late.clone(), early.clone(), middle.clone()]);
+    assert_eq!(
+        ids(QuestQuery { order: QuestOrder::Deadline, limit: 10, ..Default::default() }),
+        vec![early.clone(), middle.clone(), late.clone()],
+    );
+    assert_eq!(
+        ids(QuestQuery { order: QuestOrder::Deadline, offset: 1, limit: 1, ..Default::default() }),
+        vec![middle.clone()],
+    );
+
+    let filter = QuestFilter { quest_type: Some(QuestType::BaseTier), deadline_from: Some(200), ..Default::default() };
+    assert_eq!(ids(QuestQuery { filter, limit: 10, ..Default::default() }), vec![late]);
+
+    let filter = QuestFilter { status: Some(QuestStatus::Closed), provider: Some(PROVIDER.into()), ..Default::default() };
+    assert_eq!(ids(QuestQuery { filter, limit: 10, ..Default::default() }), vec![middle]);
+}
+
+#[test]
+fn quests_of_a_seeker_are_indexed() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let submission = submission_ref("submission");
+
+    let graded = publish(&quest, QuestType::BaseTier);
+    let waiting = publish(&quest, QuestType::Dedicated);
+    publish(&quest, QuestType::BaseTier);
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: graded.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Commit { quest_id: waiting.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: graded.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: graded.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Reject),
+            feedback: String::new(),
+        },
+    );
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert_eq!(
+        state.get_seeker_quests(SEEKER.into()),
+        vec![
+            (graded, QuestType::BaseTier, SeekerStatus::Graded(Gradings::Reject), Some(Gradings::Reject)),
+            (waiting, QuestType::Dedicated, SeekerStatus::Waiting, None),
+        ],
+    );
+    assert_eq!(state.get_seeker_quests(ANOTHER_SEEKER.into()), vec![]);
+}
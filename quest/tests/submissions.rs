@@ -0,0 +1,37 @@
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn submissions_must_pin_down_their_content() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+
+    let invalid = [
+        SubmissionRef { locator: String::from("http://drive.google.com/submission"), ..submission_ref("submission") },
+        SubmissionRef { storage: Storage::Ipfs, ..submission_ref("submission") },
+        SubmissionRef { storage: Storage::Arweave, locator: String::from("not a transaction id"), ..submission_ref("submission") },
+        SubmissionRef { content_hash: [0; 32], ..submission_ref("submission") },
+        SubmissionRef { size: 0, ..submission_ref("submission") },
+    ];
+    for submission in invalid {
+        let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission });
+        assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::InvalidSubmission))));
+    }
+
+    let submission = SubmissionRef {
+        storage: Storage::Ipfs,
+        locator: String::from("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+        ..submission_ref("submission")
+    };
+    let res = quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Submitted { quest_id, seeker: SEEKER.into() }))));
+}
@@ -0,0 +1,151 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const THIRD_SEEKER: u64 = 104;
+const FOURTH_SEEKER: u64 = 105;
+
+#[test]
+fn teams_commit_once_and_share_rewards() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { max_team_size: 3, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("submission");
+
+    let res = quest.send(SEEKER, QuestAction::CreateTeam { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::TeamCreated { quest_id: quest_id.clone(), leader: SEEKER.into() }))));
+
+    quest.send(SEEKER, QuestAction::InviteToTeam { quest_id: quest_id.clone(), member: ANOTHER_SEEKER.into() });
+    quest.send(SEEKER, QuestAction::InviteToTeam { quest_id: quest_id.clone(), member: THIRD_SEEKER.into() });
+    let res = quest.send(SEEKER, QuestAction::InviteToTeam { quest_id: quest_id.clone(), member: FOURTH_SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::TeamFull))));
+
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::AcceptInvite { quest_id: quest_id.clone(), leader: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::TeamJoined {
+        quest_id: quest_id.clone(),
+        leader: SEEKER.into(),
+        member: ANOTHER_SEEKER.into(),
+    }))));
+
+    // Members commit through their leader, which locks the team
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::InTeam))));
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    let res = quest.send(THIRD_SEEKER, QuestAction::AcceptInvite { quest_id: quest_id.clone(), leader: SEEKER.into() });
+    assert!(res.contains(&Log::builder().dest(THIRD_SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::TeamLocked))));
+
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+    quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, top_tier_quest) = state.top_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(top_tier_quest.base.capacity, 10);
+    assert_eq!(
+        state.get_seeker_quests(ANOTHER_SEEKER.into()),
+        vec![(quest_id, QuestType::TopTier, SeekerStatus::Graded(Gradings::Accept), Some(Gradings::Accept))],
+    );
+
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    assert!(balances.contains(&(ActorId::from(SEEKER), 1)));
+    assert!(balances.contains(&(ActorId::from(ANOTHER_SEEKER), 1)));
+    assert!(!balances.iter().any(|(owner, _)| *owner == ActorId::from(THIRD_SEEKER)));
+}
+
+#[test]
+fn solo_quests_have_no_teams() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    sys.mint_to(PROVIDER, PRIZE);
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, incoming_quest(), PRIZE);
+
+    let res = quest.send(SEEKER, QuestAction::CreateTeam { quest_id });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::TeamsNotSupported))));
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Publish {
+            quest_type: QuestType::BaseTier,
+            quest_info: IncomingQuest { max_team_size: 2, ..incoming_quest() },
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::TeamsNotSupported))));
+}
+
+#[test]
+fn teams_of_the_max_size_can_be_graded() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let skill_ft = add_skill_ft(&reputation);
+    let quest = init_quest(&sys, &reputation, vec![(SkillToken::Python, skill_ft)]);
+    sys.mint_to(PROVIDER, 2 * PRIZE);
+
+    // Larger teams can't be rewarded within a single grade
+    let res = quest.send_with_value(
+        PROVIDER,
+        QuestAction::Publish { quest_type: QuestType::TopTier, quest_info: IncomingQuest { max_team_size: MAX_TEAM_SIZE + 1, ..incoming_quest() } },
+        PRIZE,
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Err::<QuestEvent, QuestError>(QuestError::TeamTooLarge))));
+
+    let quest_id = publish_with_value(&quest, QuestType::TopTier, IncomingQuest { max_team_size: MAX_TEAM_SIZE, ..incoming_quest() }, PRIZE);
+    let submission = submission_ref("submission");
+    let members: Vec<u64> = (1..MAX_TEAM_SIZE as u64).map(|i| 200 + i).collect();
+
+    quest.send(SEEKER, QuestAction::CreateTeam { quest_id: quest_id.clone() });
+    for member in members.iter().copied() {
+        quest.send(SEEKER, QuestAction::InviteToTeam { quest_id: quest_id.clone(), member: member.into() });
+        let res = quest.send(member, QuestAction::AcceptInvite { quest_id: quest_id.clone(), leader: SEEKER.into() });
+        assert!(res.contains(&Log::builder().dest(member).payload(Ok::<QuestEvent, QuestError>(QuestEvent::TeamJoined {
+            quest_id: quest_id.clone(),
+            leader: SEEKER.into(),
+            member: member.into(),
+        }))));
+    }
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission.clone() });
+
+    let res = quest.send(
+        PROVIDER,
+        QuestAction::Grade {
+            quest_id: quest_id.clone(),
+            commiter: SEEKER.into(),
+            submission,
+            grading: Evaluation::Gradings(Gradings::Accept),
+            feedback: String::new(),
+        },
+    );
+    assert!(res.contains(&Log::builder().dest(PROVIDER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Graded {
+        quest_id,
+        seeker: SEEKER.into(),
+        grading: Gradings::Accept,
+    }))));
+
+    let state: reputation_io::State = reputation.read_state().expect("Failed to read state");
+    let (_, balances) = state.balances.iter().find(|(id, _)| *id == skill_ft).unwrap();
+    for recipient in core::iter::once(SEEKER).chain(members) {
+        assert!(balances.contains(&(ActorId::from(recipient), 1)));
+    }
+}
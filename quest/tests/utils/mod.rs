@@ -0,0 +1,178 @@
+#![allow(dead_code)]
+
+use gstd::{prelude::*, ActorId};
+use gtest::{Log, Program, RunResult, System};
+use quest_io::*;
+use reputation_io::{InitMTK, MTKAction, MTKError, MTKEvent, SkillFtData, SkillNftMetadata, TokenId};
+
+/// The reputation program needs to be built first with `cargo build --release` in `../reputation`.
+pub const REPUTATION_WASM: &str = "../reputation/target/wasm32-unknown-unknown/release/reputation.opt.wasm";
+
+pub const ADMIN: u64 = 100;
+pub const PROVIDER: u64 = 101;
+pub const SEEKER: u64 = 102;
+pub const ANOTHER_SEEKER: u64 = 103;
+
+/// Prize attached to top-tier quests, split by `incoming_quest()` as 60% and 40%.
+pub const PRIZE: u128 = 10_000_000_000_000;
+
+pub const SKILL_NFT_NAME: &str = "Python";
+/// Name of the reputation NFT issued by `incoming_quest()`, see `RepuNFT::name`.
+pub const REPUTATION_NFT_NAME: &str = "CSInternship";
+
+pub fn actor(program: &Program) -> ActorId {
+    program.id().into_bytes().into()
+}
+
+/// Decode the first payload sent to `to` in the run result.
+/// Broadcast events go to `ActorId::zero()`, so they are skipped.
+pub fn decode_reply<D: Decode>(res: &RunResult, to: u64) -> D {
+    let expected = Log::builder().dest(to);
+    res.log()
+        .iter()
+        .filter(|log| *log == &expected)
+        .find_map(|log| D::decode(&mut log.payload()).ok())
+        .expect("No reply found")
+}
+
+/// Deploy the reputation program with the skill NFT and reputation NFT names available.
+pub fn init_reputation(sys: &System) -> Program {
+    let reputation = Program::from_file(sys, REPUTATION_WASM);
+    let res = reputation.send(
+        ADMIN,
+        InitMTK {
+            name: String::from("Commit Reputation"),
+            symbol: String::from("CRP"),
+            base_uri: String::new(),
+        },
+    );
+    assert!(!res.main_failed());
+
+    for name in [SKILL_NFT_NAME, REPUTATION_NFT_NAME] {
+        let res = reputation.send(ADMIN, MTKAction::AddNft { name: String::from(name) });
+        assert!(!res.main_failed());
+    }
+
+    reputation
+}
+
+/// Add a skill FT to the reputation program and return its token id.
+pub fn add_skill_ft(reputation: &Program) -> TokenId {
+    let res = reputation.send(
+        ADMIN,
+        MTKAction::AddFt {
+            token_data: SkillFtData {
+                name: Some(String::from(SKILL_NFT_NAME)),
+                ..Default::default()
+            },
+        },
+    );
+
+    match decode_reply::<Result<MTKEvent, MTKError>>(&res, ADMIN) {
+        Ok(MTKEvent::NewFtAdded { id }) => id,
+        other => panic!("Unexpected reply: {:?}", other),
+    }
+}
+
+/// Mint a skill NFT to `to` and return its token id.
+pub fn mint_skill_nft(reputation: &Program, to: u64) -> TokenId {
+    mint_nft(reputation, to, SKILL_NFT_NAME)
+}
+
+/// Mint an NFT with the given title, which must be registered in the reputation program, and return its token id.
+pub fn mint_nft(reputation: &Program, to: u64, title: &str) -> TokenId {
+    let res = reputation.send(
+        ADMIN,
+        MTKAction::MintNftTo {
+            to: to.into(),
+            metadata: SkillNftMetadata {
+                owner: Some(to.into()),
+                title: Some(String::from(title)),
+                ..Default::default()
+            },
+        },
+    );
+
+    match decode_reply::<Result<MTKEvent, MTKError>>(&res, ADMIN) {
+        Ok(MTKEvent::SkillNftMinted { id, .. }) => id,
+        other => panic!("Unexpected reply: {:?}", other),
+    }
+}
+
+/// Deploy the quest program with `PROVIDER` approved and wired to the given reputation program.
+pub fn init_quest(sys: &System, reputation: &Program, skill_tokens: Vec<(SkillToken, TokenId)>) -> Program {
+    let quest = Program::current(sys);
+    let res = quest.send(
+        ADMIN,
+        InitQuest {
+            approved_providers: vec![PROVIDER.into()],
+            minumum_free_gradings: 1,
+            reputation_contract: actor(reputation),
+            skill_tokens,
+        },
+    );
+    assert!(!res.main_failed());
+
+    quest
+}
+
+pub fn incoming_quest() -> IncomingQuest {
+    IncomingQuest {
+        institution_name: String::from("Commit Labs"),
+        quest_name: String::from("Python Simulation"),
+        description: String::from("Simulate a bouncing ball in Python."),
+        deliverables: String::from("A link to the source code."),
+        capacity: 10,
+        skill_token_name: SkillToken::Python,
+        deadline: 1_000,
+        open_try: true,
+        provider_name: String::from("Alice"),
+        contact_info: String::from("alice@commit.foundation"),
+        free_gradings: 5,
+        stake: 0,
+        hiring_for: String::from("Internship"),
+        skill_tags: SkillNFT::Python,
+        reputation_nft: RepuNFT::CSInternship,
+        prize_split: vec![60, 40],
+        application_deadline: 1_000,
+        dedicated_to: None,
+        max_revisions: 0,
+        rubric: None,
+        max_team_size: 0,
+        prerequisites: vec![],
+        milestones: vec![],
+        reveal_window: 0,
+        passcode_hash: None,
+        passcode_salt: [0; 32],
+    }
+}
+
+/// A submission served over HTTPS, named after the last segment of its URL.
+pub fn submission_ref(name: &str) -> SubmissionRef {
+    SubmissionRef {
+        storage: Storage::Https,
+        locator: format!("https://drive.google.com/{name}"),
+        content_hash: [1; 32],
+        size: 1_024,
+    }
+}
+
+/// Publish a quest from `PROVIDER` and return its id.
+pub fn publish(quest: &Program, quest_type: QuestType) -> QuestId {
+    publish_with(quest, quest_type, incoming_quest())
+}
+
+/// Publish a quest with the given info from `PROVIDER` and return its id.
+pub fn publish_with(quest: &Program, quest_type: QuestType, quest_info: IncomingQuest) -> QuestId {
+    publish_with_value(quest, quest_type, quest_info, 0)
+}
+
+/// Publish a quest carrying `value` from `PROVIDER` and return its id.
+pub fn publish_with_value(quest: &Program, quest_type: QuestType, quest_info: IncomingQuest, value: u128) -> QuestId {
+    let res = quest.send_with_value(PROVIDER, QuestAction::Publish { quest_type, quest_info }, value);
+
+    match decode_reply::<Result<QuestEvent, QuestError>>(&res, PROVIDER) {
+        Ok(QuestEvent::Published { quest_id, .. }) => quest_id,
+        other => panic!("Failed to publish: {:?}", other),
+    }
+}
@@ -0,0 +1,99 @@
+use gstd::ActorId;
+use gtest::{Log, System};
+use quest_io::*;
+
+mod utils;
+use utils::*;
+
+const STAKE: u128 = 1_000_000_000_000;
+
+#[test]
+fn withdrawal_releases_the_slot_and_free_grading() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let first_nft = mint_skill_nft(&reputation, SEEKER);
+    let second_nft = mint_skill_nft(&reputation, ANOTHER_SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { capacity: 1, free_gradings: 1, ..incoming_quest() });
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(first_nft), passcode: None });
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id.clone(), QuestStatus::Full)));
+
+    let res = quest.send(SEEKER, QuestAction::Withdraw { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Withdrawn { quest_id: quest_id.clone(), seeker: SEEKER.into() }))));
+
+    let state: State = quest.read_state().expect("Failed to read state");
+    assert!(state.quest_status.contains(&(quest_id.clone(), QuestStatus::Open)));
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.base.capacity, 1);
+    assert_eq!(mid_tier_quest.free_gradings, 1);
+    assert_eq!(mid_tier_quest.base.submissions.get(&ActorId::from(SEEKER)), Some(&SeekerStatus::Withdrawn));
+    // The skill NFT is not refunded
+    assert!(state.consumed_skill_nfts.contains(&(first_nft, quest_id.clone())));
+
+    // The withdrawn seeker can't come back, but the slot goes to someone else
+    let res = quest.send(SEEKER, QuestAction::Withdraw { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotCommitted))));
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(second_nft), passcode: None });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Committed { quest_id, seeker: ANOTHER_SEEKER.into() }))));
+}
+
+#[test]
+fn withdrawal_forfeits_the_stake() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let first_nft = mint_skill_nft(&reputation, SEEKER);
+    let second_nft = mint_skill_nft(&reputation, ANOTHER_SEEKER);
+    let quest_id = publish_with(&quest, QuestType::MidTier, IncomingQuest { free_gradings: 1, stake: STAKE, ..incoming_quest() });
+    sys.mint_to(ANOTHER_SEEKER, STAKE);
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(first_nft), passcode: None });
+    quest.send_with_value(ANOTHER_SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: Some(second_nft), passcode: None }, STAKE);
+
+    let res = quest.send(ANOTHER_SEEKER, QuestAction::Withdraw { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(Ok::<QuestEvent, QuestError>(QuestEvent::Withdrawn {
+        quest_id: quest_id.clone(),
+        seeker: ANOTHER_SEEKER.into(),
+    }))));
+    assert!(!res.contains(&Log::builder().dest(ANOTHER_SEEKER).payload(QuestEvent::StakeRefunded {
+        quest_id: quest_id.clone(),
+        seeker: ANOTHER_SEEKER.into(),
+        amount: STAKE,
+    })));
+
+    // Staked seekers don't get a free grading back, and the stake stays escrowed until the deadline
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert_eq!(mid_tier_quest.free_gradings, 0);
+    assert_eq!(mid_tier_quest.base.stakes.get(&ActorId::from(ANOTHER_SEEKER)), Some(&STAKE));
+
+    sys.spend_blocks(1_000);
+    let state: State = quest.read_state().expect("Failed to read state");
+    let (_, mid_tier_quest) = state.mid_tier_quests.iter().find(|(id, _)| *id == quest_id).unwrap();
+    assert!(mid_tier_quest.base.stakes.is_empty());
+}
+
+#[test]
+fn only_waiting_seekers_can_withdraw() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let reputation = init_reputation(&sys);
+    let quest = init_quest(&sys, &reputation, vec![]);
+    let quest_id = publish(&quest, QuestType::BaseTier);
+
+    let res = quest.send(SEEKER, QuestAction::Withdraw { quest_id: quest_id.clone() });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::NotCommitted))));
+
+    quest.send(SEEKER, QuestAction::Commit { quest_id: quest_id.clone(), skill_nft: None, passcode: None });
+    quest.send(SEEKER, QuestAction::Submit { quest_id: quest_id.clone(), submission: submission_ref("submission") });
+
+    let res = quest.send(SEEKER, QuestAction::Withdraw { quest_id });
+    assert!(res.contains(&Log::builder().dest(SEEKER).payload(Err::<QuestEvent, QuestError>(QuestError::AlreadySubmitted))));
+}
@@ -29,6 +29,17 @@ pub struct MtkData {
     // for skill nfts
     pub skill_nft_metadata: HashMap<TokenId, SkillNftMetadata>,
     pub nft_owners: HashMap<ActorId, Vec<TokenId>>,
+    // monotonic counter shared by skill fts and nfts, the next token gets the current value
+    pub token_nonce: TokenId,
+}
+
+impl MtkData {
+    // Token ids stay unique even for tokens added or minted within the same block.
+    pub fn gen_token_id(&mut self) -> TokenId {
+        let id = self.token_nonce;
+        self.token_nonce += 1;
+        id
+    }
 }
 
 // For skill fungible tokens.
@@ -42,7 +53,7 @@ pub struct SkillFtData {
 	pub circulation: Option<u128>,
 }
 
-#[derive(Debug, Decode, Encode, TypeInfo, Default)]
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone)]
 pub struct SkillNftMetadata {
 	// The receipient's id
 	pub owner: Option<ActorId>,
@@ -56,7 +67,7 @@ pub struct SkillNftMetadata {
 	pub freshness: Option<u32>,
 }
 
-#[derive(Debug, Decode, Encode, TypeInfo, Default)]
+#[derive(Debug, Decode, Encode, TypeInfo, Default, Clone)]
 // All possible names of skill NFTs we can issue.
 pub struct AvailableSkillNames {
     names: Vec<String>,
@@ -88,7 +99,7 @@ impl AvailableSkillNames {
     }
 }
 
-#[derive(Encode, Decode, Debug, TypeInfo, Default)]
+#[derive(Encode, Decode, Debug, TypeInfo, Default, Clone)]
 pub struct Quest {
     // The quest's id
     pub id: Option<String>,
@@ -100,8 +111,59 @@ pub struct Quest {
     pub description: Option<String>,
     // The quest's reward
     pub reward: Option<TokenId>,
-    // The submission storage id `String` that resulted in the issuance of the skill NFT.
-    pub submission: Option<String>,
+    // The exact submission that resulted in the issuance of the skill NFT.
+    pub submission: Option<SubmissionRef>,
+}
+
+#[derive(Encode, Decode, Debug, TypeInfo, Default, Clone, PartialEq, Eq)]
+// Where a submission is stored.
+pub enum Storage {
+    // Content addressed by an IPFS CID.
+    Ipfs,
+    // Content stored permanently by an Arweave transaction.
+    Arweave,
+    // Content served from an HTTPS URL.
+    #[default]
+    Https,
+}
+
+#[derive(Encode, Decode, Debug, TypeInfo, Default, Clone, PartialEq, Eq)]
+// A reference to a submitted artifact, pinned down by the hash of its content.
+pub struct SubmissionRef {
+    pub storage: Storage,
+    // The IPFS CID, the Arweave transaction id or the HTTPS URL.
+    pub locator: String,
+    // SHA-256 hash of the content.
+    pub content_hash: [u8; 32],
+    // Size of the content in bytes.
+    pub size: u64,
+}
+
+impl SubmissionRef {
+    // Check that the locator is well-formed for its storage and the content is described.
+    pub fn is_valid(&self) -> bool {
+        if self.size == 0 || self.content_hash == [0; 32] {
+            return false;
+        }
+
+        let locator = self.locator.as_str();
+        match self.storage {
+            // CIDv0 in base58btc, or CIDv1 in lowercase base32
+            Storage::Ipfs => {
+                let base58 = |c: char| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l');
+                let base32 = |c: char| c.is_ascii_lowercase() || ('2'..='7').contains(&c);
+                (locator.len() == 46 && locator.starts_with("Qm") && locator.chars().all(base58))
+                    || (locator.len() >= 59 && locator.starts_with('b') && locator.chars().all(base32))
+            },
+            // Transaction ids are 32 bytes in base64url
+            Storage::Arweave => locator.len() == 43 && locator.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            Storage::Https => {
+                locator.len() > "https://".len()
+                    && locator.starts_with("https://")
+                    && locator.chars().all(|c| c.is_ascii_graphic())
+            },
+        }
+    }
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -168,6 +230,8 @@ pub enum MTKAction {
         skill_type: bool,
         // what reputation to verify
         token_id: TokenId,
+        // The title the skill nft must have, `None` accepts any title. Ignored for skill fts.
+        title: Option<String>,
     },
     
 
@@ -302,4 +366,5 @@ pub struct State {
     pub ft_owners: Vec<(ActorId, Vec<TokenId>)>,
     pub skill_nft_metadata: Vec<(TokenId, SkillNftMetadata)>,
     pub nft_owners: Vec<(ActorId, Vec<TokenId>)>,
+    pub token_nonce: TokenId,
 }
\ No newline at end of file
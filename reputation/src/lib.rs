@@ -1,5 +1,5 @@
 #![no_std]
-use gstd::{collections::HashMap, exec, msg, prelude::*, ActorId};
+use gstd::{collections::HashMap, msg, prelude::*, ActorId};
 use reputation_io::*;
 
 #[derive(Debug, Default)]
@@ -50,14 +50,16 @@ extern "C" fn handle() {
             target,
             skill_type,
             token_id,
-        } => mtk_contract.verify_repu(target, skill_type, token_id),
+            title,
+        } => mtk_contract.verify_repu(target, skill_type, token_id, title),
     };
     msg::reply(reply, 0).expect("Failed to encode or reply with `Result<MtkEvent, MtkError>`.");
 }
 
 #[no_mangle]
 extern "C" fn state() {
-    let contract = unsafe { CONTRACT.take().expect("Unexpected error in taking state") };
+    // Borrow the contract, so the program keeps working after its state is read
+    let contract = unsafe { CONTRACT.as_ref().expect("`Mtk` is not initialized.") };
     msg::reply::<State>(contract.into(), 0).expect(
         "Failed to encode or reply with `<ContractMetadata as Metadata>::State` from `state()`",
     );
@@ -98,7 +100,7 @@ impl Mtk {
             return Err(MTKError::SkillNameDoesNotExists);
         }
         // 1. generate a token id for the soon to be minted NFT.
-        let id = gen_token_id();
+        let id = self.tokens.gen_token_id();
         // 2. check if the token id already exists
         if self.tokens.skill_nft_metadata.contains_key(&id) {
             Err(MTKError::TokenAlreadyExists)
@@ -141,7 +143,7 @@ impl Mtk {
             return Err(MTKError::OnlyCreaterCanOperate);
         }
 
-        let id = gen_token_id();
+        let id = self.tokens.gen_token_id();
         self.tokens
             .skill_fungible_tokens
             .insert(id.clone(), token_data);
@@ -199,8 +201,17 @@ impl Mtk {
         target: ActorId,
         skill_type: bool,
         token_id: TokenId,
+        title: Option<String>,
     ) -> Result<MTKEvent, MTKError> {
         if skill_type {
+            // the nft must carry the requested title, e.g. the skill a quest requires
+            let title_matches = title.map_or(true, |title| {
+                self.tokens
+                    .skill_nft_metadata
+                    .get(&token_id)
+                    .and_then(|metadata| metadata.title.as_ref())
+                    == Some(&title)
+            });
             // search for the token id in the owner's NFTs
             if self.tokens.nft_owners.contains_key(&target) {
                 if self
@@ -209,6 +220,7 @@ impl Mtk {
                     .get(&target)
                     .unwrap()
                     .contains(&token_id)
+                    && title_matches
                 {
                     Ok(MTKEvent::RepuVerified {
                         initiator: msg::source(),
@@ -256,8 +268,8 @@ impl Mtk {
     }
 }
 
-impl From<Mtk> for State {
-    fn from(value: Mtk) -> Self {
+impl From<&Mtk> for State {
+    fn from(value: &Mtk) -> Self {
         let Mtk {
             tokens,
             creator,
@@ -273,38 +285,36 @@ impl From<Mtk> for State {
             ft_owners,
             skill_nft_metadata,
             nft_owners,
+            token_nonce,
         } = tokens;
 
         let balances = balances
-            .into_iter()
-            .map(|(k, v)| (k, v.into_iter().map(|(a, b)| (a, b)).collect()))
+            .iter()
+            .map(|(k, v)| (*k, v.iter().map(|(a, b)| (*a, *b)).collect()))
             .collect();
         let skill_fungible_tokens = skill_fungible_tokens
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (*k, v.clone()))
             .collect();
-        let ft_owners = ft_owners.into_iter().map(|(k, v)| (k, v)).collect();
+        let ft_owners = ft_owners.iter().map(|(k, v)| (*k, v.clone())).collect();
         let skill_nft_metadata = skill_nft_metadata
-            .into_iter()
-            .map(|(k, v)| (k, v))
+            .iter()
+            .map(|(k, v)| (*k, v.clone()))
             .collect();
-        let nft_owners = nft_owners.into_iter().map(|(k, v)| (k, v)).collect();
+        let nft_owners = nft_owners.iter().map(|(k, v)| (*k, v.clone())).collect();
 
         Self {
-            name,
-            symbol,
-            base_uri,
-            creator,
-            available_skill_names,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            base_uri: base_uri.clone(),
+            creator: *creator,
+            available_skill_names: available_skill_names.clone(),
             balances,
             skill_fungible_tokens,
             ft_owners,
             skill_nft_metadata,
             nft_owners,
+            token_nonce: *token_nonce,
         }
     }
 }
-
-fn gen_token_id() -> TokenId {
-    exec::block_timestamp().into()
-}
@@ -0,0 +1,48 @@
+use gstd::{prelude::*, ActorId};
+use gtest::{Program, System};
+use reputation_io::*;
+
+const CREATOR: u64 = 100;
+const USER: u64 = 101;
+
+fn mint_nft(program: &Program) {
+    let res = program.send(
+        CREATOR,
+        MTKAction::MintNftTo {
+            to: USER.into(),
+            metadata: SkillNftMetadata {
+                title: Some(String::from("Python")),
+                ..Default::default()
+            },
+        },
+    );
+    assert!(!res.main_failed());
+}
+
+#[test]
+fn reading_state_keeps_the_program_working() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let program = Program::current(&sys);
+    program.send(
+        CREATOR,
+        InitMTK {
+            name: String::from("Commit Reputation"),
+            symbol: String::from("CRP"),
+            base_uri: String::new(),
+        },
+    );
+    program.send(CREATOR, MTKAction::AddNft { name: String::from("Python") });
+    let user: ActorId = USER.into();
+
+    mint_nft(&program);
+    let state: State = program.read_state().expect("Failed to read state");
+    let (_, nfts) = state.nft_owners.iter().find(|(owner, _)| *owner == user).unwrap();
+    assert_eq!(nfts.len(), 1);
+
+    mint_nft(&program);
+    let state: State = program.read_state().expect("Failed to read state");
+    let (_, nfts) = state.nft_owners.iter().find(|(owner, _)| *owner == user).unwrap();
+    assert_eq!(nfts.len(), 2);
+}
@@ -0,0 +1,91 @@
+use gstd::{prelude::*, ActorId};
+use gtest::{Program, RunResult, System};
+use reputation_io::*;
+
+const CREATOR: u64 = 100;
+const USER: u64 = 101;
+
+fn init(sys: &System) -> Program {
+    let program = Program::current(sys);
+    let res = program.send(
+        CREATOR,
+        InitMTK {
+            name: String::from("Commit Reputation"),
+            symbol: String::from("CRP"),
+            base_uri: String::new(),
+        },
+    );
+    assert!(!res.main_failed());
+    program
+}
+
+fn decode_reply(res: &RunResult) -> Result<MTKEvent, MTKError> {
+    res.log()
+        .iter()
+        .find_map(|log| Result::<MTKEvent, MTKError>::decode(&mut log.payload()).ok())
+        .expect("No reply found")
+}
+
+#[test]
+fn fts_added_in_one_block_get_distinct_ids() {
+    let sys = System::new();
+    sys.init_logger();
+    let program = init(&sys);
+
+    let ids: Vec<TokenId> = ["Python", "Simulation"]
+        .into_iter()
+        .map(|name| {
+            let res = program.send(
+                CREATOR,
+                MTKAction::AddFt {
+                    token_data: SkillFtData {
+                        name: Some(String::from(name)),
+                        ..Default::default()
+                    },
+                },
+            );
+            match decode_reply(&res) {
+                Ok(MTKEvent::NewFtAdded { id }) => id,
+                other => panic!("Unexpected reply: {:?}", other),
+            }
+        })
+        .collect();
+    assert_ne!(ids[0], ids[1]);
+
+    let state: State = program.read_state().expect("Failed to read state");
+    assert_eq!(state.skill_fungible_tokens.len(), 2);
+    assert_eq!(state.balances.len(), 2);
+}
+
+#[test]
+fn nfts_minted_in_one_block_get_distinct_ids() {
+    let sys = System::new();
+    sys.init_logger();
+    let program = init(&sys);
+    program.send(CREATOR, MTKAction::AddNft { name: String::from("Python") });
+
+    let ids: Vec<TokenId> = (0..2)
+        .map(|_| {
+            let res = program.send(
+                CREATOR,
+                MTKAction::MintNftTo {
+                    to: USER.into(),
+                    metadata: SkillNftMetadata {
+                        title: Some(String::from("Python")),
+                        ..Default::default()
+                    },
+                },
+            );
+            match decode_reply(&res) {
+                Ok(MTKEvent::SkillNftMinted { id, .. }) => id,
+                other => panic!("Unexpected reply: {:?}", other),
+            }
+        })
+        .collect();
+    assert_ne!(ids[0], ids[1]);
+
+    let state: State = program.read_state().expect("Failed to read state");
+    let user: ActorId = USER.into();
+    let (_, nfts) = state.nft_owners.iter().find(|(owner, _)| *owner == user).unwrap();
+    assert_eq!(nfts, &ids);
+}